@@ -1,163 +1,484 @@
-use std::{cell::RefCell, rc::Rc, str::FromStr};
+use std::{error::Error, fmt::Display};
 
-use lazy_static::lazy_static;
-use regex::Regex;
+use shared::Dot;
 
 pub fn run(input: Vec<String>) {
     println!("Part 1: {}", part1(&input));
     println!("Part 2: {}", part2(&input));
 }
 
-enum Command {
-    ChangeDir(String),
-    List,
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum FileSystemError {
+    /** `cd` into a directory that no listing has shown */
+    UnknownDirectory {
+        line: usize,
+        path: String,
+    },
+    /** `cd` into something listed as a file */
+    NotADirectory {
+        line: usize,
+        path: String,
+    },
+    InvalidLine {
+        line: usize,
+        text: String,
+    },
 }
 
-#[derive(Clone)]
-struct File {
+impl Display for FileSystemError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FileSystemError::UnknownDirectory { line, path } => {
+                write!(f, "line {line}: no such directory {path}")
+            }
+            FileSystemError::NotADirectory { line, path } => {
+                write!(f, "line {line}: {path} is a file, not a directory")
+            }
+            FileSystemError::InvalidLine { line, text } => {
+                write!(f, "line {line}: can't parse {text:?}")
+            }
+        }
+    }
+}
+
+impl Error for FileSystemError {}
+
+#[derive(Debug)]
+enum NodeKind {
+    File,
+    Directory { children: Vec<usize> },
+}
+
+#[derive(Debug)]
+struct Node {
     name: String,
-    is_directory: bool,
-    children: Vec<Rc<RefCell<File>>>,
+    parent: Option<usize>,
+    kind: NodeKind,
+    /** File size, or for directories the cached total of everything beneath them */
     size: usize,
-    parent: Option<Rc<RefCell<File>>>,
 }
 
-fn parse_command(command: &str) -> Command {
-    lazy_static! {
-        static ref RE: Regex = Regex::new(r"\$ (\S+)( \S+)?").unwrap();
+/** A file tree stored in an arena, where nodes refer to each other by index */
+#[derive(Debug)]
+struct FileSystem {
+    nodes: Vec<Node>,
+}
+
+impl FileSystem {
+    const ROOT: usize = 0;
+
+    /** Replays the terminal output, building the tree it describes */
+    fn parse(input: &[String]) -> Result<FileSystem, FileSystemError> {
+        let mut file_system = FileSystem {
+            nodes: vec![Node {
+                name: String::from("/"),
+                parent: None,
+                kind: NodeKind::Directory { children: vec![] },
+                size: 0,
+            }],
+        };
+        let mut cwd = FileSystem::ROOT;
+
+        for (index, text) in input.iter().enumerate() {
+            let line = index + 1;
+            let invalid = || FileSystemError::InvalidLine {
+                line,
+                text: text.clone(),
+            };
+
+            match text.split(' ').collect::<Vec<_>>()[..] {
+                ["$", "cd", "/"] => cwd = FileSystem::ROOT,
+                // Like a shell, `cd ..` at the root stays there
+                ["$", "cd", ".."] => cwd = file_system.nodes[cwd].parent.unwrap_or(cwd),
+                ["$", "cd", name] => {
+                    let path = || file_system.join(cwd, name);
+
+                    cwd = match file_system.child(cwd, name) {
+                        Some(child) if file_system.is_directory(child) => child,
+                        Some(_) => {
+                            return Err(FileSystemError::NotADirectory { line, path: path() })
+                        }
+                        None => {
+                            return Err(FileSystemError::UnknownDirectory { line, path: path() })
+                        }
+                    };
+                }
+                ["$", "ls"] => {}
+                ["dir", name] => {
+                    file_system.add(cwd, name, NodeKind::Directory { children: vec![] }, 0);
+                }
+                [size, name] => {
+                    let size = size.parse().map_err(|_| invalid())?;
+                    file_system.add(cwd, name, NodeKind::File, size);
+                }
+                _ => return Err(invalid()),
+            }
+        }
+
+        // Children are always added after their parent, so walking backwards
+        // finishes each directory's total before it's added to its parent's
+        for index in (1..file_system.nodes.len()).rev() {
+            let node = &file_system.nodes[index];
+            let (parent, size) = (node.parent.unwrap(), node.size);
+
+            file_system.nodes[parent].size += size;
+        }
+
+        Ok(file_system)
     }
 
-    let caps = RE.captures(command).unwrap();
-    let command_text = caps.get(1).unwrap().as_str();
-    let args = caps.get(2).map(|value| value.as_str());
+    /** Adds a node under `parent`, unless a listing already added one with that name */
+    fn add(&mut self, parent: usize, name: &str, kind: NodeKind, size: usize) {
+        if self.child(parent, name).is_some() {
+            return;
+        }
+
+        let index = self.nodes.len();
+        self.nodes.push(Node {
+            name: name.to_string(),
+            parent: Some(parent),
+            kind,
+            size,
+        });
 
-    match command_text {
-        "cd" => Command::ChangeDir(String::from_str(args.unwrap().trim()).unwrap()),
-        _ => Command::List,
+        if let NodeKind::Directory { children } = &mut self.nodes[parent].kind {
+            children.push(index);
+        }
     }
-}
 
-fn count_files(result: &mut Vec<usize>, file: &File) -> usize {
-    let size = if file.is_directory {
-        let mut sum = 0;
-        file.children
-            .iter()
-            .for_each(|it| sum += count_files(result, &it.borrow()));
+    fn children(&self, index: usize) -> &[usize] {
+        match &self.nodes[index].kind {
+            NodeKind::Directory { children } => children,
+            NodeKind::File => &[],
+        }
+    }
 
-        sum
-    } else {
-        file.size
-    };
+    fn child(&self, index: usize, name: &str) -> Option<usize> {
+        self.children(index)
+            .iter()
+            .copied()
+            .find(|&child| self.nodes[child].name == name)
+    }
 
-    if file.is_directory {
-        result.push(size);
+    fn is_directory(&self, index: usize) -> bool {
+        matches!(self.nodes[index].kind, NodeKind::Directory { .. })
     }
 
-    size
-}
+    /** Finds the node at an absolute path such as `/a/e` */
+    fn lookup(&self, path: &str) -> Option<usize> {
+        path.split('/')
+            .filter(|part| !part.is_empty())
+            .try_fold(FileSystem::ROOT, |index, name| self.child(index, name))
+    }
 
-fn parse_file(file: &str, parent: Rc<RefCell<File>>) -> File {
-    let mut iter = file.split(' ');
-    let dir_or_size = iter.next().unwrap();
-    let name = iter.next().unwrap();
-
-    if dir_or_size == "dir" {
-        File {
-            name: String::from_str(name).unwrap(),
-            children: vec![],
-            size: 0,
-            is_directory: true,
-            parent: Some(parent),
+    /** The absolute path of a node */
+    fn path(&self, index: usize) -> String {
+        match self.nodes[index].parent {
+            Some(parent) => self.join(parent, &self.nodes[index].name),
+            None => String::from("/"),
         }
-    } else {
-        File {
-            name: String::from_str(name).unwrap(),
-            children: vec![],
-            size: dir_or_size.parse::<usize>().unwrap(),
-            is_directory: false,
-            parent: Some(parent),
+    }
+
+    fn join(&self, parent: usize, name: &str) -> String {
+        format!("{}/{name}", self.path(parent).trim_end_matches('/'))
+    }
+
+    /** Every directory's total size, in tree order */
+    fn directory_sizes(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.nodes.len())
+            .filter(|&index| self.is_directory(index))
+            .map(|index| self.nodes[index].size)
+    }
+
+    /** The smallest directory whose deletion frees up enough space for the update */
+    fn directory_to_delete(&self) -> Option<usize> {
+        let free_space = 70000000 - self.nodes[FileSystem::ROOT].size;
+
+        (0..self.nodes.len())
+            .filter(|&index| self.is_directory(index))
+            .filter(|&index| free_space + self.nodes[index].size >= 30000000)
+            .min_by_key(|&index| self.nodes[index].size)
+    }
+
+    /** Directories from `start` down, with human-readable total sizes, indented by depth like `du -h` */
+    fn du_report(&self, start: usize) -> String {
+        let mut report = String::new();
+        let mut stack = vec![(start, 0)];
+
+        while let Some((index, depth)) = stack.pop() {
+            let node = &self.nodes[index];
+            let name = if index == start {
+                self.path(index)
+            } else {
+                format!("{}/", node.name)
+            };
+
+            report.push_str(&format!(
+                "{:>6}  {}{name}\n",
+                human_size(node.size),
+                "  ".repeat(depth)
+            ));
+
+            for &child in self.children(index).iter().rev() {
+                if self.is_directory(child) {
+                    stack.push((child, depth + 1));
+                }
+            }
         }
+
+        report
     }
-}
 
-fn build_tree(input: &[String]) -> Rc<RefCell<File>> {
-    let root = File {
-        name: String::from("/"),
-        size: 0,
-        children: vec![],
-        is_directory: true,
-        parent: None,
-    };
-    let root = Rc::new(RefCell::new(root));
-    let mut cwd = root.clone();
-    let mut iter = input.iter();
-
-    while let Some(line) = iter.next() {
-        if line.starts_with('$') {
-            match parse_command(line) {
-                Command::ChangeDir(arg) => match arg.as_str() {
-                    "/" => {
-                        cwd = root.clone();
-                    }
-                    ".." => {
-                        let parent = cwd.borrow().parent.as_ref().cloned().unwrap();
-                        cwd = parent;
-                    }
-                    _ => {
-                        let child = cwd
-                            .borrow()
-                            .children
-                            .iter()
-                            .find(|it| it.borrow().name == arg)
-                            .unwrap()
-                            .clone();
-                        cwd = child;
-                    }
-                },
-                Command::List => {
-                    let ls_iter = iter.clone().take_while(|it| !it.starts_with('$'));
-                    for line in ls_iter {
-                        let file = parse_file(line, cwd.clone());
-                        cwd.borrow_mut().children.push(Rc::new(RefCell::new(file)));
-                        iter.next(); // Advance overall iterator
-                    }
+    /** The tree as a DOT digraph keyed by path, highlighting the directory part 2 deletes */
+    fn to_dot(&self) -> Dot {
+        let mut dot = Dot::digraph("filesystem");
+        let to_delete = self.directory_to_delete();
+
+        for (index, node) in self.nodes.iter().enumerate() {
+            let path = self.path(index);
+
+            if self.is_directory(index) {
+                let dot_node = dot
+                    .add_node(&path)
+                    .label(format!("{} ({})", node.name, node.size))
+                    .shape("folder");
+
+                if to_delete == Some(index) {
+                    dot_node.highlight();
                 }
+            } else {
+                dot.add_node(&path)
+                    .label(format!("{} {}", node.name, node.size));
+            }
+
+            if let Some(parent) = node.parent {
+                dot.add_edge(&self.path(parent), &path);
             }
         }
+
+        dot
+    }
+}
+
+/** Formats a byte count with a binary unit suffix, as `du -h` does */
+fn human_size(bytes: usize) -> String {
+    let mut size = bytes as f64;
+    let mut units = ["K", "M", "G", "T"].iter();
+    let mut unit = "";
+
+    while size >= 1024.0 {
+        match units.next() {
+            Some(next) => unit = next,
+            None => break,
+        }
+
+        size /= 1024.0;
     }
 
-    root
+    if unit.is_empty() {
+        bytes.to_string()
+    } else if size < 10.0 {
+        format!("{size:.1}{unit}")
+    } else {
+        format!("{size:.0}{unit}")
+    }
 }
 
-fn part1(input: &[String]) -> usize {
-    let root = build_tree(input);
+fn parse_input(input: &[String]) -> FileSystem {
+    FileSystem::parse(input).unwrap_or_else(|error| panic!("Invalid terminal output: {error}"))
+}
 
-    let mut result = 0;
-    let mut small_files = vec![];
-    count_files(&mut small_files, &root.borrow());
+/** Prints the `du -h` style report of the directory at `path`, or of the whole tree */
+pub fn report(input: Vec<String>, path: Option<&str>) {
+    let file_system = parse_input(&input);
+    let path = path.unwrap_or("/");
 
-    for val in small_files {
-        if val < 100000 {
-            result += val;
+    match file_system.lookup(path) {
+        Some(index) if file_system.is_directory(index) => {
+            print!("{}", file_system.du_report(index))
         }
+        Some(_) => eprintln!("{path} is a file, not a directory"),
+        None => eprintln!("No such directory: {path}"),
     }
+}
+
+/** The file tree as a DOT digraph, highlighting the directory part 2 deletes */
+pub fn export_graph(input: &[String]) -> Dot {
+    parse_input(input).to_dot()
+}
+
+fn part1(input: &[String]) -> usize {
+    let file_system = parse_input(input);
 
-    result
+    file_system
+        .directory_sizes()
+        .filter(|&size| size < 100000)
+        .sum()
 }
 
 fn part2(input: &[String]) -> usize {
-    let root = build_tree(input);
+    let file_system = parse_input(input);
 
-    let mut small_files = vec![];
-    let used_space = count_files(&mut small_files, &root.borrow());
-    small_files.sort();
-    let free_space = 70000000 - used_space;
+    file_system
+        .directory_to_delete()
+        .map_or(0, |index| file_system.nodes[index].size)
+}
 
-    for val in small_files {
-        if (free_space + val) >= 30000000 {
-            return val;
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(input: &str) -> Vec<String> {
+        input
+            .lines()
+            .map(|line| line.trim().to_string())
+            .filter(|line| !line.is_empty())
+            .collect()
+    }
+
+    fn example() -> Vec<String> {
+        lines(
+            "
+            $ cd /
+            $ ls
+            dir a
+            14848514 b.txt
+            8504156 c.dat
+            dir d
+            $ cd a
+            $ ls
+            dir e
+            29116 f
+            2557 g
+            62596 h.lst
+            $ cd e
+            $ ls
+            584 i
+            $ cd ..
+            $ cd ..
+            $ cd d
+            $ ls
+            4060174 j
+            8033020 d.log
+            5626152 d.ext
+            7214296 k
+            ",
+        )
+    }
+
+    #[test]
+    fn example_answers() {
+        assert_eq!(part1(&example()), 95437);
+        assert_eq!(part2(&example()), 24933642);
+    }
+
+    #[test]
+    fn lookup_paths() {
+        let file_system = FileSystem::parse(&example()).unwrap();
+        let e = file_system.lookup("/a/e").unwrap();
+
+        assert_eq!(file_system.nodes[e].size, 584);
+        assert_eq!(file_system.path(e), "/a/e");
+        assert_eq!(file_system.lookup("/a/e/"), Some(e));
+        assert_eq!(file_system.lookup("/"), Some(FileSystem::ROOT));
+        assert!(!file_system.is_directory(file_system.lookup("/d/k").unwrap()));
+        assert_eq!(file_system.lookup("/a/x"), None);
+    }
+
+    #[test]
+    fn du_report() {
+        let file_system = FileSystem::parse(&example()).unwrap();
+
+        assert_eq!(
+            file_system.du_report(FileSystem::ROOT),
+            "   46M  /\n   93K    a/\n   584      e/\n   24M    d/\n"
+        );
+        assert_eq!(
+            file_system.du_report(file_system.lookup("/a").unwrap()),
+            "   93K  /a\n   584    e/\n"
+        );
     }
 
-    0
+    #[test]
+    fn human_sizes() {
+        assert_eq!(human_size(0), "0");
+        assert_eq!(human_size(1023), "1023");
+        assert_eq!(human_size(1024), "1.0K");
+        assert_eq!(human_size(1536), "1.5K");
+        assert_eq!(human_size(10 * 1024), "10K");
+        assert_eq!(human_size(5 << 30), "5.0G");
+    }
+
+    #[test]
+    fn errors() {
+        let error = |input: &str| FileSystem::parse(&lines(input)).unwrap_err();
+
+        assert_eq!(
+            error("$ cd /\n$ ls\ndir a\n$ cd b"),
+            FileSystemError::UnknownDirectory {
+                line: 4,
+                path: String::from("/b")
+            }
+        );
+        assert_eq!(
+            error("$ cd /\n$ ls\n10 a\n$ cd a\n$ ls\n5 b"),
+            FileSystemError::NotADirectory {
+                line: 4,
+                path: String::from("/a")
+            }
+        );
+        assert_eq!(
+            error("$ cd /\n$ ls\nten a"),
+            FileSystemError::InvalidLine {
+                line: 3,
+                text: String::from("ten a")
+            }
+        );
+        assert_eq!(
+            error("$ cd /\n$ rm -rf a").to_string(),
+            "line 2: can't parse \"$ rm -rf a\""
+        );
+    }
+
+    #[test]
+    fn export_highlights_only_the_deleted_directory() {
+        let dot = export_graph(&example()).to_string();
+        let highlighted = dot
+            .lines()
+            .filter(|line| line.contains("fillcolor"))
+            .collect::<Vec<_>>();
+
+        assert_eq!(highlighted.len(), 1);
+        assert!(highlighted[0].starts_with("    \"/d\" [label=\"d (24933642)\""));
+        assert!(dot.contains("    \"/a\" -> \"/a/e\";"));
+    }
+
+    #[test]
+    fn export_highlights_one_of_equal_sizes() {
+        // /a has the same total as its only child, and both are small enough
+        let input = lines(
+            "
+            $ cd /
+            $ ls
+            dir a
+            100 x
+            $ cd a
+            $ ls
+            dir b
+            $ cd b
+            $ ls
+            10 c
+            ",
+        );
+        let file_system = FileSystem::parse(&input).unwrap();
+
+        assert_eq!(file_system.directory_to_delete(), file_system.lookup("/a"));
+        assert_eq!(
+            export_graph(&input)
+                .to_string()
+                .matches("fillcolor")
+                .count(),
+            1
+        );
+    }
 }
@@ -1,75 +1,37 @@
 use std::collections::HashSet;
 
+use shared::{Direction, Point};
+
 pub fn run(input: Vec<String>) {
     println!("Part 1: {}", part1(&input));
     println!("Part 2: {}", part2(&input));
 }
 
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-type Position = (i32, i32);
-
 struct Rope {
-    knots: Vec<Position>,
-    tail_positions: HashSet<Position>,
+    knots: Vec<Point>,
+    tail_positions: HashSet<Point>,
 }
 
 impl Rope {
     fn new(num_knots: usize) -> Rope {
         Rope {
-            knots: vec![(0, 0); num_knots],
-            tail_positions: HashSet::from_iter([(0, 0)]),
+            knots: vec![Point::ORIGIN; num_knots],
+            tail_positions: HashSet::from_iter([Point::ORIGIN]),
         }
     }
 
     fn move_head(&mut self, direction: Direction, distance: usize) {
         for _ in 0..distance {
-            let head = &mut self.knots[0];
-
-            match direction {
-                Direction::Up => {
-                    head.1 += 1;
-                }
-                Direction::Down => {
-                    head.1 -= 1;
-                }
-                Direction::Left => {
-                    head.0 -= 1;
-                }
-                Direction::Right => {
-                    head.0 += 1;
-                }
-            }
+            self.knots[0] = self.knots[0].step(direction);
 
             for i in 1..self.knots.len() {
                 let head = self.knots[i - 1];
                 let tail = &mut self.knots[i];
 
-                let dx = head.0 - tail.0;
-                let dy = head.1 - tail.1;
-
-                // Drifted horizontally
-                if dx.abs() == 2 {
-                    // Diagonal
-                    if dy.abs() > 0 {
-                        tail.1 += if dy > 0 { 1 } else { -1 };
-                    }
-
-                    tail.0 += if dx > 0 { 1 } else { -1 };
-                }
-                // Drifted vertically
-                else if dy.abs() == 2 {
-                    // Diagonal
-                    if dx.abs() > 0 {
-                        tail.0 += if dx > 0 { 1 } else { -1 };
-                    }
-
-                    tail.1 += if dy > 0 { 1 } else { -1 };
+                // Only follow once the knots are no longer touching, moving at most one step
+                // along each axis (diagonally if needed)
+                if head.chebyshev_distance(tail) > 1 {
+                    *tail += (head - *tail).signum();
                 }
             }
 
@@ -118,3 +80,32 @@ fn part2(input: &[String]) -> usize {
 
     rope.tail_positions.len()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(input: &str) -> Vec<String> {
+        input
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .chunks(2)
+            .map(|motion| motion.join(" "))
+            .collect()
+    }
+
+    #[test]
+    fn small_example() {
+        let input = lines("R 4 U 4 L 3 D 1 R 4 D 1 L 5 R 2");
+
+        assert_eq!(part1(&input), 13);
+        assert_eq!(part2(&input), 1);
+    }
+
+    #[test]
+    fn larger_example() {
+        let input = lines("R 5 U 8 L 8 D 3 R 17 D 10 L 25 U 20");
+
+        assert_eq!(part2(&input), 36);
+    }
+}
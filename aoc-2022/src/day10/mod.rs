@@ -1,5 +1,6 @@
-use core::panic;
-use std::collections::VecDeque;
+use std::fmt::{self, Display};
+
+use shared::vm::{Flow, Instruction as VmInstruction, Machine};
 
 pub fn run(input: Vec<String>) {
     println!("Part 1: {}", part1(&input));
@@ -13,61 +14,57 @@ enum Instruction {
     AddX(i32),
 }
 
-struct Cpu {
-    cycle: usize,
-    register: i32,
-    /** The currently executing instruction */
-    instruction: Option<Instruction>,
-    program: VecDeque<Instruction>,
+#[derive(Debug, Clone, Copy)]
+struct Registers {
+    x: i32,
 }
 
-impl Cpu {
-    fn new() -> Cpu {
-        Cpu {
-            cycle: 0,
-            register: 1,
-            instruction: None,
-            program: VecDeque::new(),
+impl Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Instruction::Noop => write!(f, "noop"),
+            Instruction::AddX(arg) => write!(f, "addx {arg}"),
         }
     }
+}
 
-    fn tick(&mut self) {
-        // Start cycle
-        match self.instruction {
-            Some(inst) => match inst {
-                Instruction::AddX(arg) => {
-                    self.register += arg;
-                    self.instruction = None;
-                }
-                _ => panic!("Can't execute instruction"),
-            },
-            None => {
-                let inst = self.program.pop_front();
-                self.instruction = inst;
-            }
+impl VmInstruction for Instruction {
+    type State = Registers;
+    type Output = ();
+
+    fn execute(&self, registers: &mut Registers, _output: &mut Vec<()>) -> Flow {
+        if let Instruction::AddX(arg) = self {
+            registers.x += arg;
         }
 
-        // End cycle
-        self.cycle += 1;
+        Flow::Next
+    }
 
-        // Remove Noop at the end of the cycle
-        if let Some(inst) = self.instruction {
-            if inst == Instruction::Noop {
-                self.instruction = None;
-            }
+    fn cycles(&self) -> usize {
+        match self {
+            Instruction::Noop => 1,
+            Instruction::AddX(_) => 2,
         }
     }
+}
 
-    fn load(&mut self, program: &[Instruction]) {
-        self.program = VecDeque::from_iter(program.iter().copied())
-    }
+/** Runs the program, calling `during` with every cycle number (from 1) and the X register's value during it */
+fn run_cycles(program: Vec<Instruction>, mut during: impl FnMut(usize, i32)) {
+    let mut machine = Machine::new(program, Registers { x: 1 });
 
-    fn is_done(&self) -> bool {
-        self.instruction.is_none() && self.program.is_empty()
-    }
+    loop {
+        // X only changes once an instruction completes, so it holds its
+        // value for every cycle the instruction takes
+        let x = machine.state().x;
+        let first_cycle = machine.cycle() + 1;
 
-    fn signal(&self) -> i32 {
-        (self.cycle as i32) * self.register
+        if !machine.step() {
+            break;
+        }
+
+        for cycle in first_cycle..=machine.cycle() {
+            during(cycle, x);
+        }
     }
 }
 
@@ -100,9 +97,9 @@ impl Crt {
         }
     }
 
-    fn draw(&mut self, cpu: &Cpu) {
-        let sprite_position = cpu.register;
-        let pixel = cpu.cycle;
+    /** Draws the pixel for `cycle` (from 1) with the sprite centred on `sprite_position` */
+    fn draw(&mut self, cycle: usize, sprite_position: i32) {
+        let pixel = cycle - 1;
         let row = pixel / 40;
         let col = pixel % 40;
 
@@ -114,44 +111,93 @@ impl Crt {
         };
     }
 
+    fn render(&self) -> String {
+        self.buffer
+            .iter()
+            .map(|row| row.iter().collect::<String>() + "\n")
+            .collect()
+    }
+
     fn display(&self) {
-        for row in self.buffer {
-            for col in row {
-                print!("{col}");
-            }
+        print!("{}", self.render());
+    }
+}
 
-            println!();
-        }
+/** Runs the program, printing its disassembly and every instruction executed */
+pub fn trace(input: Vec<String>) {
+    let mut machine = Machine::new(parse_input(&input), Registers { x: 1 });
+    machine.enable_trace();
+    machine.run();
+
+    println!("{}", machine.disassemble());
+    println!();
+
+    for entry in machine.trace() {
+        println!("{entry}");
     }
 }
 
 fn part1(input: &[String]) -> i32 {
     let program = parse_input(input);
-    let mut cpu = Cpu::new();
-    cpu.load(&program);
     let mut result = 0;
 
-    while !cpu.is_done() {
-        cpu.tick();
-
-        if [20, 60, 100, 140, 180, 220].contains(&cpu.cycle) {
-            result += cpu.signal()
+    run_cycles(program, |cycle, x| {
+        if [20, 60, 100, 140, 180, 220].contains(&cycle) {
+            result += cycle as i32 * x;
         }
-    }
+    });
 
     result
 }
 
-fn part2(input: &[String]) {
+fn draw_screen(input: &[String]) -> Crt {
     let program = parse_input(input);
-    let mut cpu = Cpu::new();
     let mut crt = Crt::new();
-    cpu.load(&program);
 
-    while !cpu.is_done() {
-        crt.draw(&cpu);
-        cpu.tick();
+    run_cycles(program, |cycle, x| crt.draw(cycle, x));
+
+    crt
+}
+
+fn part2(input: &[String]) {
+    draw_screen(input).display();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Vec<String> {
+        include_str!("example.txt")
+            .lines()
+            .map(String::from)
+            .collect()
+    }
+
+    #[test]
+    fn signal_strength_samples_during_cycles() {
+        assert_eq!(part1(&example()), 13140);
     }
 
-    crt.display();
+    #[test]
+    fn crt_image() {
+        let image = draw_screen(&example())
+            .render()
+            .replace('█', "#")
+            .replace('░', ".");
+
+        assert_eq!(
+            image,
+            [
+                "##..##..##..##..##..##..##..##..##..##..",
+                "###...###...###...###...###...###...###.",
+                "####....####....####....####....####....",
+                "#####.....#####.....#####.....#####.....",
+                "######......######......######......####",
+                "#######.......#######.......#######.....",
+                "",
+            ]
+            .join("\n")
+        );
+    }
 }
@@ -1,4 +1,6 @@
-use std::{collections::VecDeque, vec};
+use std::{cmp::Reverse, collections::VecDeque, vec};
+
+use shared::math::lcm;
 
 pub fn run(input: Vec<String>) {
     println!("Part 1: {}", part1(&input));
@@ -150,7 +152,7 @@ fn part1(input: &[String]) -> u64 {
         }
     }
 
-    monkeys.sort_by(|a, b| b.inspections_made.cmp(&a.inspections_made));
+    monkeys.sort_by_key(|monkey| Reverse(monkey.inspections_made));
 
     monkeys
         .iter()
@@ -160,9 +162,10 @@ fn part1(input: &[String]) -> u64 {
 
 fn part2(input: &[String]) -> u64 {
     let mut monkeys = parse_input(input);
+    // Tests only care about worry levels modulo each divisor, so they can be kept modulo the lcm
     let mod_val = monkeys
         .iter()
-        .fold(1, |acc, monkey| acc * monkey.test.divisor);
+        .fold(1, |acc, monkey| lcm(acc, monkey.test.divisor));
 
     // 10,000 rounds
     for _ in 1..=10_000 {
@@ -175,7 +178,7 @@ fn part2(input: &[String]) -> u64 {
         }
     }
 
-    monkeys.sort_by(|a, b| b.inspections_made.cmp(&a.inspections_made));
+    monkeys.sort_by_key(|monkey| Reverse(monkey.inspections_made));
 
     monkeys
         .iter()
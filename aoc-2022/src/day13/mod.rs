@@ -1,10 +1,16 @@
-use std::{cmp::Ordering, fmt::Debug, str::Chars, vec};
+use std::{
+    cmp::Ordering,
+    error::Error,
+    fmt::{self, Debug, Display},
+    str::FromStr,
+};
 
 pub fn run(input: Vec<String>) {
     println!("Part 1: {}", part1(&input));
     println!("Part 2: {}", part2(&input));
 }
 
+/** A packet value. Equality follows the puzzle's ordering, so `[1]` equals `1` */
 #[derive(Clone, Debug)]
 enum ListItem {
     Integer(i32),
@@ -20,93 +26,183 @@ impl PartialEq for ListItem {
 impl Eq for ListItem {}
 
 impl PartialOrd for ListItem {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
 }
 
 impl Ord for ListItem {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+    fn cmp(&self, other: &Self) -> Ordering {
         match (self, other) {
             (ListItem::Integer(left), ListItem::Integer(right)) => left.cmp(right),
-            (ListItem::List(left), ListItem::List(right)) => {
-                let max_index = left.len().min(right.len());
+            (ListItem::List(left), ListItem::List(right)) => left.cmp(right),
+            (ListItem::Integer(_), ListItem::List(right)) => {
+                std::slice::from_ref(self).cmp(right.as_slice())
+            }
+            (ListItem::List(left), ListItem::Integer(_)) => {
+                left.as_slice().cmp(std::slice::from_ref(other))
+            }
+        }
+    }
+}
 
-                for i in 0..max_index {
-                    let left_child = &left[i];
-                    let right_child = &right[i];
+impl Display for ListItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ListItem::Integer(value) => write!(f, "{value}"),
+            ListItem::List(items) => {
+                write!(f, "[")?;
 
-                    match left_child.cmp(right_child) {
-                        Ordering::Less => return Ordering::Less,
-                        Ordering::Greater => return Ordering::Greater,
-                        _ => {}
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
                     }
+
+                    write!(f, "{item}")?;
                 }
 
-                left.len().cmp(&right.len())
-            }
-            (ListItem::Integer(left), ListItem::List(right)) => {
-                ListItem::List(vec![ListItem::Integer(*left)]).cmp(&ListItem::List(right.clone()))
-            }
-            (ListItem::List(left), ListItem::Integer(right)) => {
-                ListItem::List(left.clone()).cmp(&ListItem::List(vec![ListItem::Integer(*right)]))
+                write!(f, "]")
             }
         }
     }
 }
 
-fn parse_list(char_iter: &mut Chars) -> Vec<ListItem> {
-    let mut res = vec![];
-    let mut temp_str: Option<String> = None;
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ParsePacketError {
+    /** 1-based column of the offending character, or one past the end */
+    column: usize,
+    expected: &'static str,
+    found: Option<char>,
+}
 
-    while let Some(character) = char_iter.next() {
-        match character {
-            '[' => {
-                res.push(ListItem::List(parse_list(char_iter)));
-            }
-            '0'..='9' => {
-                if let Some(temp_str) = &mut temp_str {
-                    temp_str.push(character);
-                } else {
-                    temp_str = Some(String::from(character));
-                }
-            }
-            ',' | ']' => {
-                if let Some(unwrapped) = temp_str {
-                    res.push(ListItem::Integer(unwrapped.parse().unwrap()));
-                    temp_str = None;
-                }
+impl Display for ParsePacketError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "column {}: expected {}, found ",
+            self.column, self.expected
+        )?;
+
+        match self.found {
+            Some(character) => write!(f, "{character:?}"),
+            None => write!(f, "end of packet"),
+        }
+    }
+}
+
+impl Error for ParsePacketError {}
+
+/** Recursive-descent parser over the packet's bytes, tracking the position for errors */
+struct Parser<'a> {
+    text: &'a str,
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.text[self.position..].chars().next()
+    }
+
+    fn error(&self, expected: &'static str) -> ParsePacketError {
+        ParsePacketError {
+            column: self.position + 1,
+            expected,
+            found: self.peek(),
+        }
+    }
+
+    fn expect(&mut self, character: char, expected: &'static str) -> Result<(), ParsePacketError> {
+        if self.peek() != Some(character) {
+            return Err(self.error(expected));
+        }
+
+        self.position += 1;
+        Ok(())
+    }
+
+    fn item(&mut self) -> Result<ListItem, ParsePacketError> {
+        match self.peek() {
+            Some('[') => self.list(),
+            Some('0'..='9') => self.integer(),
+            _ => Err(self.error("'[' or a digit")),
+        }
+    }
 
-                if character == ']' {
-                    break;
+    fn list(&mut self) -> Result<ListItem, ParsePacketError> {
+        self.expect('[', "'['")?;
+        let mut items = vec![];
+
+        if self.peek() == Some(']') {
+            self.position += 1;
+            return Ok(ListItem::List(items));
+        }
+
+        loop {
+            items.push(self.item()?);
+
+            match self.peek() {
+                Some(',') => self.position += 1,
+                Some(']') => {
+                    self.position += 1;
+                    return Ok(ListItem::List(items));
                 }
+                _ => return Err(self.error("',' or ']'")),
             }
-            _ => {}
         }
     }
 
-    res
+    fn integer(&mut self) -> Result<ListItem, ParsePacketError> {
+        let start = self.position;
+        let length = self.text[start..]
+            .bytes()
+            .take_while(u8::is_ascii_digit)
+            .count();
+
+        let value = self.text[start..start + length]
+            .parse()
+            .map_err(|_| self.error("an integer that fits in 32 bits"))?;
+        self.position += length;
+
+        Ok(ListItem::Integer(value))
+    }
 }
 
-fn parse_line(line: &str) -> ListItem {
-    let mut chars = line.chars();
-    chars.next(); // Skip first '['
+impl FromStr for ListItem {
+    type Err = ParsePacketError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser { text, position: 0 };
+        let packet = parser.item()?;
+
+        if parser.position < text.len() {
+            return Err(parser.error("end of packet"));
+        }
 
-    ListItem::List(parse_list(&mut chars))
+        Ok(packet)
+    }
 }
 
-fn parse_input(input: &[String]) -> Vec<(ListItem, ListItem)> {
-    let mut iter = input.iter();
-    let mut result = vec![];
+fn parse_line(line_number: usize, line: &str) -> ListItem {
+    line.parse()
+        .unwrap_or_else(|error| panic!("Invalid packet on line {line_number}: {error}"))
+}
 
-    while let Some(left) = iter.next() {
-        let right = iter.next().unwrap();
-        iter.next(); // Consume empty line
+fn parse_input(input: &[String]) -> Vec<(ListItem, ListItem)> {
+    let packets = input
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(i, line)| parse_line(i + 1, line))
+        .collect::<Vec<_>>();
 
-        result.push((parse_line(left), parse_line(right)));
+    if packets.len() % 2 != 0 {
+        panic!("Packets should come in pairs, found {}", packets.len());
     }
 
-    result
+    packets
+        .chunks(2)
+        .map(|pair| (pair[0].clone(), pair[1].clone()))
+        .collect()
 }
 
 fn part1(input: &[String]) -> u32 {
@@ -139,3 +235,117 @@ fn part2(input: &[String]) -> u32 {
 
     (divider_1_index * divider_2_index) as u32
 }
+
+#[cfg(test)]
+mod tests {
+    use shared::XorShift;
+
+    use super::*;
+
+    /** A random packet, with small integers so that comparisons often tie */
+    fn random_packet(rng: &mut XorShift, depth: usize) -> ListItem {
+        if depth == 0 || rng.below(3) == 0 {
+            return ListItem::Integer(rng.below(4) as i32);
+        }
+
+        let length = rng.below(4);
+
+        ListItem::List((0..length).map(|_| random_packet(rng, depth - 1)).collect())
+    }
+
+    fn random_packets(count: usize) -> Vec<ListItem> {
+        let mut rng = XorShift::new(0x2022_1213);
+
+        (0..count).map(|_| random_packet(&mut rng, 4)).collect()
+    }
+
+    #[test]
+    fn example() {
+        let input = "
+            [1,1,3,1,1]
+            [1,1,5,1,1]
+
+            [[1],[2,3,4]]
+            [[1],4]
+
+            [9]
+            [[8,7,6]]
+
+            [[4,4],4,4]
+            [[4,4],4,4,4]
+
+            [7,7,7,7]
+            [7,7,7]
+
+            []
+            [3]
+
+            [[[]]]
+            [[]]
+
+            [1,[2,[3,[4,[5,6,7]]]],8,9]
+            [1,[2,[3,[4,[5,6,0]]]],8,9]";
+        let input = input
+            .lines()
+            .skip(1)
+            .map(|line| line.trim().to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(part1(&input), 13);
+        assert_eq!(part2(&input), 140);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = |text: &str| text.parse::<ListItem>().unwrap_err().to_string();
+
+        assert_eq!(
+            error("[1,,2]"),
+            "column 4: expected '[' or a digit, found ','"
+        );
+        assert_eq!(
+            error("[1,2"),
+            "column 5: expected ',' or ']', found end of packet"
+        );
+        assert_eq!(error("[1]]"), "column 4: expected end of packet, found ']'");
+        assert_eq!(error("[1 2]"), "column 3: expected ',' or ']', found ' '");
+        assert_eq!(
+            error("[99999999999]"),
+            "column 2: expected an integer that fits in 32 bits, found '9'"
+        );
+        assert_eq!(
+            error(""),
+            "column 1: expected '[' or a digit, found end of packet"
+        );
+    }
+
+    #[test]
+    fn display_round_trips() {
+        for packet in random_packets(500) {
+            let text = packet.to_string();
+            let parsed = text.parse::<ListItem>().unwrap();
+
+            // `==` follows the ordering, which can't tell `[1]` from `1`
+            assert_eq!(format!("{parsed:?}"), format!("{packet:?}"), "{text}");
+        }
+    }
+
+    #[test]
+    fn ordering_is_total() {
+        let packets = random_packets(60);
+
+        for a in &packets {
+            assert_eq!(a.cmp(a), Ordering::Equal, "{a}");
+
+            for b in &packets {
+                assert_eq!(a.cmp(b), b.cmp(a).reverse(), "{a} vs {b}");
+
+                for c in &packets {
+                    if a <= b && b <= c {
+                        assert!(a <= c, "{a} <= {b} <= {c}");
+                    }
+                }
+            }
+        }
+    }
+}
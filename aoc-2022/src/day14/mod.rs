@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use shared::{Point, SparseGrid};
 
 pub fn run(input: Vec<String>) {
     println!("Part 1: {}", part1(&input));
@@ -12,12 +12,9 @@ enum Material {
     Sand,
 }
 
-#[derive(Eq, PartialEq, Hash)]
-struct Position(u32, u32);
-
 struct Slice {
-    data: HashMap<Position, Material>,
-    height: u32,
+    data: SparseGrid<Material>,
+    height: i64,
     sand_count: u32,
     include_floor: bool,
 }
@@ -27,40 +24,38 @@ impl Slice {
         Slice {
             height: 0,
             sand_count: 0,
-            data: HashMap::new(),
+            data: SparseGrid::new(Material::Air),
             include_floor: false,
         }
     }
 
-    fn get(&self, Position(x_pos, y_pos): Position) -> Material {
-        *self.data.get(&Position(x_pos, y_pos)).unwrap_or_else(|| {
-            if self.include_floor && y_pos == self.height - 1 {
-                return &Material::Rock;
-            }
+    fn get(&self, position: Point) -> Material {
+        if !self.data.contains(&position) && self.include_floor && position.y == self.height - 1 {
+            return Material::Rock;
+        }
 
-            &Material::Air
-        })
+        *self.data.get(&position)
     }
 
-    fn set(&mut self, position: Position, value: Material) {
+    fn set(&mut self, position: Point, value: Material) {
         self.data.insert(position, value);
     }
 
-    fn drop_sand(&mut self, Position(x_pos, y_pos): Position) -> bool {
+    fn drop_sand(&mut self, Point { x: x_pos, y: y_pos }: Point) -> bool {
         for y in y_pos..(self.height - 1) {
             // Find lowest Y where the next space isn't empty
-            if self.get(Position(x_pos, y + 1)) == Material::Air {
+            if self.get(Point::new(x_pos, y + 1)) == Material::Air {
                 continue;
             }
 
-            if self.get(Position(x_pos - 1, y + 1)) == Material::Air {
+            if self.get(Point::new(x_pos - 1, y + 1)) == Material::Air {
                 // Left diagonal is empty, place there
-                return self.drop_sand(Position(x_pos - 1, y + 1));
-            } else if self.get(Position(x_pos + 1, y + 1)) == Material::Air {
+                return self.drop_sand(Point::new(x_pos - 1, y + 1));
+            } else if self.get(Point::new(x_pos + 1, y + 1)) == Material::Air {
                 // Right diagonal is empty, place there
-                return self.drop_sand(Position(x_pos + 1, y + 1));
-            } else if self.get(Position(x_pos, y)) == Material::Air {
-                self.set(Position(x_pos, y), Material::Sand);
+                return self.drop_sand(Point::new(x_pos + 1, y + 1));
+            } else if self.get(Point::new(x_pos, y)) == Material::Air {
+                self.set(Point::new(x_pos, y), Material::Sand);
                 self.sand_count += 1;
                 return true;
             } else {
@@ -73,7 +68,7 @@ impl Slice {
 }
 
 fn parse_input(input: &[String], include_floor: bool) -> Slice {
-    let line_segments: Vec<Vec<(u32, u32)>> = input
+    let line_segments: Vec<Vec<(i64, i64)>> = input
         .iter()
         .map(|line_segment| {
             line_segment
@@ -89,24 +84,22 @@ fn parse_input(input: &[String], include_floor: bool) -> Slice {
                 .collect()
         })
         .collect();
-    let mut y_max = 0;
     let mut slice = Slice::new();
 
     for vertices in line_segments {
         for window in vertices.windows(2) {
             let v1 = window[0];
             let v2 = window[1];
-            y_max = y_max.max(v1.1).max(v2.1);
 
             for x in v1.0.min(v2.0)..=v1.0.max(v2.0) {
                 for y in v1.1.min(v2.1)..=v1.1.max(v2.1) {
-                    slice.data.insert(Position(x, y), Material::Rock);
+                    slice.set(Point::new(x, y), Material::Rock);
                 }
             }
         }
     }
 
-    slice.height = y_max + 1;
+    slice.height = slice.data.bounds().map_or(0, |(_, max)| max.y) + 1;
 
     if include_floor {
         slice.height += 2;
@@ -119,7 +112,7 @@ fn parse_input(input: &[String], include_floor: bool) -> Slice {
 fn part1(input: &[String]) -> u32 {
     let mut slice = parse_input(input, false);
 
-    while slice.drop_sand(Position(500, 0)) {}
+    while slice.drop_sand(Point::new(500, 0)) {}
 
     slice.sand_count
 }
@@ -127,7 +120,31 @@ fn part1(input: &[String]) -> u32 {
 fn part2(input: &[String]) -> u32 {
     let mut slice = parse_input(input, true);
 
-    while slice.drop_sand(Position(500, 0)) {}
+    while slice.drop_sand(Point::new(500, 0)) {}
 
     slice.sand_count
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Vec<String> {
+        [
+            "498,4 -> 498,6 -> 496,6",
+            "503,4 -> 502,4 -> 502,9 -> 494,9",
+        ]
+        .map(String::from)
+        .to_vec()
+    }
+
+    #[test]
+    fn sand_until_abyss() {
+        assert_eq!(part1(&example()), 24);
+    }
+
+    #[test]
+    fn sand_until_source_blocked() {
+        assert_eq!(part2(&example()), 93);
+    }
+}
@@ -3,6 +3,8 @@ use std::vec;
 use lazy_static::lazy_static;
 use regex::Regex;
 
+use shared::{Direction, IntervalSet, Point};
+
 pub fn run(input: Vec<String>) {
     println!("Part 1: {}", part1(&input));
     println!("Part 2: {}", part2(&input));
@@ -16,60 +18,35 @@ enum MapCell {
     NotBeacon,
 }
 
-#[derive(Hash, Eq, PartialEq, Clone, Copy)]
-struct Position(i32, i32);
-
 struct Vertices {
-    top: Position,
-    bottom: Position,
-    left: Position,
-    right: Position,
+    top: Point,
+    bottom: Point,
+    left: Point,
+    right: Point,
 }
 
 struct Edges {
-    top_left: (Position, Position),
-    top_right: (Position, Position),
-    bottom_left: (Position, Position),
-    bottom_right: (Position, Position),
-}
-
-impl Position {
-    fn abs_distance(&self, other: Position) -> u32 {
-        self.0.abs_diff(other.0) + self.1.abs_diff(other.1)
-    }
-
-    fn up(&self) -> Position {
-        Position(self.0, self.1 - 1)
-    }
-
-    fn down(&self) -> Position {
-        Position(self.0, self.1 + 1)
-    }
-
-    fn left(&self) -> Position {
-        Position(self.0 - 1, self.1)
-    }
-
-    fn right(&self) -> Position {
-        Position(self.0 + 1, self.1)
-    }
+    top_left: (Point, Point),
+    top_right: (Point, Point),
+    bottom_left: (Point, Point),
+    bottom_right: (Point, Point),
 }
 
 struct Sensor {
-    position: Position,
-    radius: u32,
+    position: Point,
+    radius: u64,
 }
 
-fn find_intersection(edge1: (Position, Position), edge2: (Position, Position)) -> Option<Position> {
-    let step = if edge1.0 .1 < edge1.1 .1 { 1 } else { -1 };
-    let mut y = edge1.0 .1;
+fn find_intersection(edge1: (Point, Point), edge2: (Point, Point)) -> Option<Point> {
+    let step = if edge1.0.y < edge1.1.y { 1 } else { -1 };
+    let mut y = edge1.0.y;
 
-    for x in (edge1.0 .0)..=(edge1.1 .1) {
-        let delta_x = (edge2.0).0.abs_diff(x);
-        let delta_y = (edge2.0).1.abs_diff(y);
+    for x in edge1.0.x..=edge1.1.y {
+        let delta_x = edge2.0.x.abs_diff(x);
+        let delta_y = edge2.0.y.abs_diff(y);
 
         if delta_x == delta_y {
-            return Some(Position(x, y));
+            return Some(Point::new(x, y));
         }
 
         y += step;
@@ -79,7 +56,7 @@ fn find_intersection(edge1: (Position, Position), edge2: (Position, Position)) -
 }
 
 impl Sensor {
-    fn intersections(&self, other: &Sensor) -> Vec<Position> {
+    fn intersections(&self, other: &Sensor) -> Vec<Point> {
         let mut result = vec![];
 
         let self_edges = self.edges();
@@ -118,29 +95,17 @@ impl Sensor {
 
     fn vertices(&self) -> Vertices {
         Vertices {
-            top: Position {
-                1: self.position.1 - self.radius as i32,
-                ..self.position
-            },
-            bottom: Position {
-                1: self.position.1 + self.radius as i32,
-                ..self.position
-            },
-            left: Position {
-                0: self.position.0 - self.radius as i32,
-                ..self.position
-            },
-            right: Position {
-                0: self.position.0 + self.radius as i32,
-                ..self.position
-            },
+            top: self.position + Point::new(0, -(self.radius as i64)),
+            bottom: self.position + Point::new(0, self.radius as i64),
+            left: self.position + Point::new(-(self.radius as i64), 0),
+            right: self.position + Point::new(self.radius as i64, 0),
         }
     }
 }
 
 struct Map {
     sensors: Vec<Sensor>,
-    beacons: Vec<Position>,
+    beacons: Vec<Point>,
 }
 
 impl Map {
@@ -160,13 +125,13 @@ impl Map {
         for line in input.iter() {
             let captures = RE.captures(line).unwrap();
 
-            let sensor_x = captures.get(1).unwrap().as_str().parse::<i32>().unwrap();
-            let sensor_y = captures.get(2).unwrap().as_str().parse::<i32>().unwrap();
-            let beacon_x = captures.get(3).unwrap().as_str().parse::<i32>().unwrap();
-            let beacon_y = captures.get(4).unwrap().as_str().parse::<i32>().unwrap();
+            let sensor_x = captures.get(1).unwrap().as_str().parse::<i64>().unwrap();
+            let sensor_y = captures.get(2).unwrap().as_str().parse::<i64>().unwrap();
+            let beacon_x = captures.get(3).unwrap().as_str().parse::<i64>().unwrap();
+            let beacon_y = captures.get(4).unwrap().as_str().parse::<i64>().unwrap();
 
-            let sensor = Position(sensor_x, sensor_y);
-            let beacon = Position(beacon_x, beacon_y);
+            let sensor = Point::new(sensor_x, sensor_y);
+            let beacon = Point::new(beacon_x, beacon_y);
 
             map.add_sensor(sensor, beacon);
         }
@@ -174,7 +139,7 @@ impl Map {
         map
     }
 
-    fn get(&self, position: Position) -> MapCell {
+    fn get(&self, position: Point) -> MapCell {
         if self.beacons.contains(&position) {
             return MapCell::Beacon;
         }
@@ -184,7 +149,7 @@ impl Map {
                 return MapCell::Sensor;
             }
 
-            if sensor.position.abs_distance(position) <= sensor.radius {
+            if sensor.position.manhattan_distance(&position) <= sensor.radius {
                 return MapCell::NotBeacon;
             }
         }
@@ -192,69 +157,53 @@ impl Map {
         MapCell::Unknown
     }
 
-    fn add_sensor(&mut self, sensor_position: Position, closest_beacon_position: Position) {
+    fn add_sensor(&mut self, sensor_position: Point, closest_beacon_position: Point) {
         self.sensors.push(Sensor {
             position: sensor_position,
-            radius: sensor_position.abs_distance(closest_beacon_position),
+            radius: sensor_position.manhattan_distance(&closest_beacon_position),
         });
         self.beacons.push(closest_beacon_position);
     }
 
-    fn get_row(&self, y: i32) -> Vec<MapCell> {
-        let ((min_x, max_x), _) = self.get_bounds();
-
-        let mut result = vec![];
-
-        for x in min_x..=max_x {
-            result.push(self.get(Position(x, y)));
-        }
+    /** Points on row `y` within range of any sensor */
+    fn row_coverage(&self, y: i64) -> IntervalSet {
+        self.sensors
+            .iter()
+            .filter_map(|sensor| {
+                let reach = sensor.radius.checked_sub(sensor.position.y.abs_diff(y))? as i64;
+                let x = sensor.position.x;
 
-        result
+                Some((x - reach)..=(x + reach))
+            })
+            .collect()
     }
 
-    fn get_bounds(&self) -> ((i32, i32), (i32, i32)) {
-        let mut min_x = i32::MAX;
-        let mut max_x = i32::MIN;
-        let mut min_y = i32::MAX;
-        let mut max_y = i32::MIN;
-
-        for Position(x, y) in self.beacons.iter() {
-            min_x = min_x.min(*x);
-            max_x = max_x.max(*x);
-            min_y = min_y.min(*y);
-            max_y = max_y.max(*y);
-        }
+    /** Points on row `y` that can't hold an undetected beacon */
+    fn count_not_beacon(&self, y: i64) -> u64 {
+        let mut coverage = self.row_coverage(y);
 
-        for sensor in self.sensors.iter() {
-            let sensor_bound_min_x = sensor.position.0 - sensor.radius as i32;
-            let sensor_bound_max_x = sensor.position.0 + sensor.radius as i32;
-            let sensor_bound_min_y = sensor.position.1 - sensor.radius as i32;
-            let sensor_bound_max_y = sensor.position.1 + sensor.radius as i32;
-
-            min_x = min_x.min(sensor_bound_min_x);
-            max_x = max_x.max(sensor_bound_max_x);
-            min_y = min_y.min(sensor_bound_min_y);
-            max_y = max_y.max(sensor_bound_max_y);
+        // Known sensors and beacons aren't counted
+        for &Point { x, .. } in self
+            .beacons
+            .iter()
+            .chain(self.sensors.iter().map(|sensor| &sensor.position))
+            .filter(|position| position.y == y)
+        {
+            coverage.remove(x..=x);
         }
 
-        ((min_x, max_x), (min_y, max_y))
+        coverage.len()
     }
 }
 
-fn part1(input: &[String]) -> u32 {
+fn part1(input: &[String]) -> u64 {
     let map = Map::parse(input);
 
-    map.get_row(2_000_000).iter().fold(0, |acc, it| {
-        if *it == MapCell::NotBeacon {
-            return acc + 1;
-        }
-
-        acc
-    })
+    map.count_not_beacon(2_000_000)
 }
 
 fn part2(input: &[String]) -> u64 {
-    const LIMIT: u32 = 4_000_000;
+    const LIMIT: i64 = 4_000_000;
     let map = Map::parse(input);
     let mut areas_to_check = vec![];
 
@@ -275,17 +224,50 @@ fn part2(input: &[String]) -> u64 {
     }
 
     for area in areas_to_check {
-        let positions = [area.up(), area.down(), area.left(), area.right()];
-
-        for position in positions
-            .iter()
-            .filter(|it| it.0 >= 0 && it.0 <= LIMIT as i32 && it.1 >= 0 && it.1 <= LIMIT as i32)
+        for position in [
+            Direction::Up,
+            Direction::Down,
+            Direction::Left,
+            Direction::Right,
+        ]
+        .into_iter()
+        .map(|direction| area.step(direction))
+        .filter(|it| (0..=LIMIT).contains(&it.x) && (0..=LIMIT).contains(&it.y))
         {
-            if map.get(*position) == MapCell::Unknown {
-                return (position.0 as u64 * LIMIT as u64) + position.1 as u64;
+            if map.get(position) == MapCell::Unknown {
+                return (position.x * LIMIT + position.y) as u64;
             }
         }
     }
 
     panic!("Beacon not found!");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_row() {
+        let input = [
+            "Sensor at x=2, y=18: closest beacon is at x=-2, y=15",
+            "Sensor at x=9, y=16: closest beacon is at x=10, y=16",
+            "Sensor at x=13, y=2: closest beacon is at x=15, y=3",
+            "Sensor at x=12, y=14: closest beacon is at x=10, y=16",
+            "Sensor at x=10, y=20: closest beacon is at x=10, y=16",
+            "Sensor at x=14, y=17: closest beacon is at x=10, y=16",
+            "Sensor at x=8, y=7: closest beacon is at x=2, y=10",
+            "Sensor at x=2, y=0: closest beacon is at x=2, y=10",
+            "Sensor at x=0, y=11: closest beacon is at x=2, y=10",
+            "Sensor at x=20, y=14: closest beacon is at x=25, y=17",
+            "Sensor at x=17, y=20: closest beacon is at x=21, y=22",
+            "Sensor at x=16, y=7: closest beacon is at x=15, y=3",
+            "Sensor at x=14, y=3: closest beacon is at x=15, y=3",
+            "Sensor at x=20, y=1: closest beacon is at x=15, y=3",
+        ]
+        .map(String::from);
+        let map = Map::parse(&input);
+
+        assert_eq!(map.count_not_beacon(10), 26);
+    }
+}
@@ -16,6 +16,8 @@ pub mod day15;
 
 use std::fs;
 
+use shared::Dot;
+
 /// Runs the solution for a specific day
 pub fn run_day(day: u32, input_path: &str) {
     let input = fs::read_to_string(input_path)
@@ -42,3 +44,42 @@ pub fn run_day(day: u32, input_path: &str) {
         _ => eprintln!("Day {day} not implemented for 2022"),
     }
 }
+
+/// Runs the day's program on its VM, printing the disassembly and an execution trace
+pub fn trace_day(day: u32, input_path: &str) {
+    let input = fs::read_to_string(input_path)
+        .unwrap_or_else(|_| panic!("Failed to read input file: {input_path}"));
+
+    let lines: Vec<String> = input.lines().map(|s| s.to_string()).collect();
+
+    match day {
+        10 => day10::trace(lines),
+        _ => eprintln!("Day {day} has no VM program to trace for 2022"),
+    }
+}
+
+/// Builds the day's puzzle structure as a DOT graph, if it has one to draw
+pub fn export_graph(day: u32, input_path: &str) -> Option<Dot> {
+    let input = fs::read_to_string(input_path)
+        .unwrap_or_else(|_| panic!("Failed to read input file: {input_path}"));
+
+    let lines: Vec<String> = input.lines().map(|s| s.to_string()).collect();
+
+    match day {
+        7 => Some(day07::export_graph(&lines)),
+        _ => None,
+    }
+}
+
+/// Prints the day's report on its puzzle structure, starting from `path` where the day supports one
+pub fn report_day(day: u32, input_path: &str, path: Option<&str>) {
+    let input = fs::read_to_string(input_path)
+        .unwrap_or_else(|_| panic!("Failed to read input file: {input_path}"));
+
+    let lines: Vec<String> = input.lines().map(|s| s.to_string()).collect();
+
+    match day {
+        7 => day07::report(lines, path),
+        _ => eprintln!("Day {day} has no report for 2022"),
+    }
+}
@@ -1,209 +1,121 @@
-use shared::Solution;
+use shared::{Coordinate, Grid, Solution};
 
 pub struct Day4;
 
-const SEARCH_STRING: &str = "XMAS";
-const SEARCH_STRING_REV: &str = "SAMX";
+const SEARCH_STRING: &[u8] = b"XMAS";
 
-struct Grid {
-    cells: Vec<Vec<char>>,
+fn parse_input(input: &str) -> Grid<u8> {
+    Grid::parse(input).unwrap()
 }
 
-impl Grid {
-    fn parse(input: &str) -> Grid {
-        let cells: Vec<Vec<_>> = input.lines().map(|line| line.chars().collect()).collect();
-
-        Grid { cells }
-    }
-
-    fn solve(&self) -> u32 {
-        self.find_horizontal() + self.find_vertical() + self.find_diagonal()
-    }
-
-    fn rows_len(&self) -> usize {
-        self.cells.len()
-    }
-
-    fn cols_len(&self) -> usize {
-        self.cells[0].len()
-    }
-
-    fn find_horizontal(&self) -> u32 {
-        let mut result = 0;
-
-        self.cells.iter().for_each(|row| {
-            let mut iterator = row.iter();
-
-            while iterator.len() >= 4 {
-                let chars = iterator.clone().take(4);
-                let string = chars.collect::<String>();
-
-                if string == SEARCH_STRING || string == SEARCH_STRING_REV {
-                    result += 1;
-                }
-
-                iterator.next();
-            }
-        });
-
-        result
-    }
-
-    fn find_vertical(&self) -> u32 {
-        if self.rows_len() < 4 {
-            return 0;
-        }
-
-        let mut result = 0;
-
-        let mut col = 0;
-
-        while col < self.cols_len() {
-            let mut row = 0;
-            while row < self.rows_len() - 3 {
-                let mut chars = [' '; 4];
-                chars[0] = self.cells[row][col];
-                chars[1] = self.cells[row + 1][col];
-                chars[2] = self.cells[row + 2][col];
-                chars[3] = self.cells[row + 3][col];
-
-                let string = chars.iter().collect::<String>();
-
-                if string == SEARCH_STRING || string == SEARCH_STRING_REV {
-                    result += 1;
-                }
+/** The grid in all four orientations, so searching one way covers every direction */
+fn rotations(grid: &Grid<u8>) -> [Grid<u8>; 4] {
+    [
+        grid.clone(),
+        grid.rotate_90(),
+        grid.rotate_180(),
+        grid.rotate_270(),
+    ]
+}
 
-                row += 1;
+/** Counts XMAS reading left to right, and diagonally down and to the right */
+fn count_xmas(grid: &Grid<u8>) -> usize {
+    let horizontal = grid
+        .rows()
+        .map(|row| {
+            row.windows(SEARCH_STRING.len())
+                .filter(|&window| window == SEARCH_STRING)
+                .count()
+        })
+        .sum::<usize>();
+
+    let mut diagonal = 0;
+    let length = SEARCH_STRING.len() - 1;
+
+    for row in 0..grid.row_count().saturating_sub(length) {
+        for col in 0..grid.col_count().saturating_sub(length) {
+            if SEARCH_STRING
+                .iter()
+                .enumerate()
+                .all(|(i, char)| grid.get(&Coordinate::new(row + i, col + i)) == char)
+            {
+                diagonal += 1;
             }
-
-            col += 1;
         }
-
-        result
     }
 
-    fn find_diagonal(&self) -> u32 {
-        if self.cols_len() < 4 || self.rows_len() < 4 {
-            return 0;
-        }
-
-        let mut result = 0;
-
-        let mut col = 0;
-
-        while col < self.cols_len() {
-            let mut row = 0;
-            while row < self.rows_len() - 3 {
-                let mut chars = [' '; 4];
-
-                if col < self.cols_len() - 3 {
-                    chars[0] = self.cells[row][col];
-                    chars[1] = self.cells[row + 1][col + 1];
-                    chars[2] = self.cells[row + 2][col + 2];
-                    chars[3] = self.cells[row + 3][col + 3];
-
-                    let string = chars.iter().collect::<String>();
-
-                    if string == SEARCH_STRING || string == SEARCH_STRING_REV {
-                        result += 1;
-                    }
-                }
-
-                if col > 2 {
-                    chars[0] = self.cells[row][col];
-                    chars[1] = self.cells[row + 1][col - 1];
-                    chars[2] = self.cells[row + 2][col - 2];
-                    chars[3] = self.cells[row + 3][col - 3];
-
-                    let string = chars.iter().collect::<String>();
-
-                    if string == SEARCH_STRING || string == SEARCH_STRING_REV {
-                        result += 1;
-                    }
-                }
+    horizontal + diagonal
+}
 
-                row += 1;
+/** Counts MAS crosses with both M's on the top row */
+fn count_x_mas(grid: &Grid<u8>) -> usize {
+    let mut result = 0;
+
+    for row in 0..grid.row_count().saturating_sub(2) {
+        for col in 0..grid.col_count().saturating_sub(2) {
+            let window = grid.subgrid(&Coordinate::new(row, col), 3, 3);
+            let at = |row, col| *window.get(&Coordinate::new(row, col));
+
+            if at(1, 1) == b'A'
+                && at(0, 0) == b'M'
+                && at(0, 2) == b'M'
+                && at(2, 0) == b'S'
+                && at(2, 2) == b'S'
+            {
+                result += 1;
             }
-
-            col += 1;
         }
-
-        result
     }
 
-    fn find_x_mas(&self) -> u32 {
-        if self.cols_len() < 3 || self.rows_len() < 3 {
-            return 0;
-        }
-
-        let mut result = 0;
-
-        let mut col = 1;
-
-        while col < self.cols_len() - 1 {
-            let mut row = 1;
-            while row < self.rows_len() - 1 {
-                let mut chars1 = [' '; 3];
-                let mut chars2 = [' '; 3];
-
-                chars1[0] = self.cells[row - 1][col - 1];
-                chars1[1] = self.cells[row][col];
-                chars1[2] = self.cells[row + 1][col + 1];
-                chars2[0] = self.cells[row - 1][col + 1];
-                chars2[1] = self.cells[row][col];
-                chars2[2] = self.cells[row + 1][col - 1];
-
-                let string1 = chars1.iter().collect::<String>();
-                let string2 = chars2.iter().collect::<String>();
-
-                if (string1 == "MAS" || string1 == "SAM") && (string2 == "MAS" || string2 == "SAM")
-                {
-                    result += 1;
-                }
-
-                row += 1;
-            }
-
-            col += 1;
-        }
-
-        result
-    }
+    result
 }
 
 impl Solution for Day4 {
     fn part1(&self, input: &str) -> String {
-        let grid = Grid::parse(input);
+        let grid = parse_input(input);
 
-        grid.solve().to_string()
+        rotations(&grid)
+            .iter()
+            .map(count_xmas)
+            .sum::<usize>()
+            .to_string()
     }
 
     fn part2(&self, input: &str) -> String {
-        let grid = Grid::parse(input);
+        let grid = parse_input(input);
 
-        grid.find_x_mas().to_string()
+        rotations(&grid)
+            .iter()
+            .map(count_x_mas)
+            .sum::<usize>()
+            .to_string()
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use dedent::dedent;
+
     use super::*;
 
     #[test]
-    fn test() {
-        let input = "
-            X..S
-            MMAA
-            AMAM
-            X..S
+    fn test_day4() {
+        let input = dedent!(
             "
-        .lines()
-        .map(|line| line.trim())
-        .fold(String::new(), |acc, curr| format!("{acc}{curr}\n"));
-        let grid = Grid::parse(input.trim());
-
-        let result = grid.find_diagonal();
+            MMMSXXMASM
+            MSAMXMSMSA
+            AMXSXMAAMM
+            MSAMASMSMX
+            XMASAMXAMM
+            XXAMMXXAMA
+            SMSMSASXSS
+            SAXAMASAAA
+            MAMMMXMMMM
+            MXMXAXMASX
+            "
+        );
 
-        assert!(result == 18, "Result = {result}");
+        assert_eq!(Day4.part1(input), "18");
+        assert_eq!(Day4.part2(input), "9");
     }
 }
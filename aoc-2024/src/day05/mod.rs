@@ -1,18 +1,16 @@
-use std::collections::{HashMap, HashSet};
-
-use shared::Solution;
+use shared::{Graph, Solution};
 
 pub struct Day5;
 
 struct PrintQueue {
-    ordering_rules: HashMap<u32, Vec<u32>>,
+    ordering_rules: Graph<u32>,
     updates: Vec<Vec<u32>>,
 }
 
 impl PrintQueue {
     fn parse_input(input: &str) -> PrintQueue {
         let mut lines = input.lines();
-        let mut ordering_rules: HashMap<u32, Vec<u32>> = HashMap::new();
+        let mut ordering_rules = Graph::new();
 
         lines
             .by_ref()
@@ -22,9 +20,7 @@ impl PrintQueue {
                 let from: u32 = split.next().unwrap().parse().unwrap();
                 let to: u32 = split.next().unwrap().parse().unwrap();
 
-                ordering_rules.entry(from).or_default();
-
-                ordering_rules.get_mut(&from).unwrap().push(to);
+                ordering_rules.add_edge(from, to);
             });
 
         let updates = lines
@@ -41,52 +37,21 @@ impl PrintQueue {
         }
     }
 
-    fn is_valid_update(&self, update: &Vec<u32>) -> bool {
-        let mut printed_pages = HashSet::new();
-
-        for page in update {
-            if !printed_pages.is_empty() {
-                if let Some(must_be_before) = self.ordering_rules.get(page) {
-                    if must_be_before.iter().any(|it| printed_pages.contains(it)) {
-                        return false;
-                    }
-                }
-            }
-
-            printed_pages.insert(page);
-        }
-
-        true
+    /** Whether no rule puts a later page of the update before an earlier one */
+    fn is_valid(&self, update: &[u32]) -> bool {
+        update.iter().enumerate().all(|(i, earlier)| {
+            update[i + 1..]
+                .iter()
+                .all(|later| !self.ordering_rules.has_edge(later, earlier))
+        })
     }
 
-    fn fix_update(&self, update: &[u32]) -> Vec<u32> {
-        let mut printed_pages = HashSet::new();
-        let mut result = update.to_owned();
-
-        for i in 0..result.len() {
-            let page = result[i];
-
-            if !printed_pages.is_empty() {
-                if let Some(must_be_before_list) = self.ordering_rules.get(&page) {
-                    for must_be_before in must_be_before_list {
-                        if printed_pages.contains(must_be_before) {
-                            let error_index = i;
-                            if let Some(must_be_before_index) =
-                                result.iter().position(|it| it == must_be_before)
-                            {
-                                result.insert(must_be_before_index, page);
-                                result.remove(error_index + 1);
-                                break;
-                            }
-                        }
-                    }
-                }
-            }
-
-            printed_pages.insert(page);
-        }
-
-        result
+    /** The update's pages in the order the rules between them require */
+    fn sorted_update(&self, update: &[u32]) -> Vec<u32> {
+        // The full rule set is cyclic, but the rules between any one update's pages aren't
+        self.ordering_rules
+            .topological_sort_of(update.iter().copied())
+            .expect("Ordering rules for an update should be acyclic")
     }
 }
 
@@ -97,7 +62,7 @@ impl Solution for Day5 {
         print_queue
             .updates
             .iter()
-            .filter(|update| print_queue.is_valid_update(update))
+            .filter(|update| print_queue.is_valid(update))
             .map(|update| {
                 let mid = update.len() / 2;
 
@@ -109,19 +74,11 @@ impl Solution for Day5 {
 
     fn part2(&self, input: &str) -> String {
         let print_queue = PrintQueue::parse_input(input.trim());
-        let invalid_updates = print_queue.updates.iter().filter_map(|update| {
-            if print_queue.is_valid_update(update) {
-                None
-            } else {
-                let mut fixed = update.clone();
-
-                while !print_queue.is_valid_update(&fixed) {
-                    fixed = print_queue.fix_update(&fixed);
-                }
-
-                Some(fixed)
-            }
-        });
+        let invalid_updates = print_queue
+            .updates
+            .iter()
+            .filter(|update| !print_queue.is_valid(update))
+            .map(|update| print_queue.sorted_update(update));
 
         invalid_updates
             .map(|update| {
@@ -133,3 +90,49 @@ impl Solution for Day5 {
             .to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use dedent::dedent;
+
+    use super::*;
+
+    #[test]
+    fn test_day05() {
+        let input = dedent!(
+            "
+            47|53
+            97|13
+            97|61
+            97|47
+            75|29
+            61|13
+            75|53
+            29|13
+            97|29
+            53|29
+            61|53
+            97|53
+            61|29
+            47|13
+            75|47
+            97|75
+            47|61
+            75|61
+            47|29
+            75|13
+            53|13
+
+            75,47,61,53,29
+            97,61,53,29,13
+            75,29,13
+            75,97,47,61,53
+            61,13,29
+            97,13,75,29,47
+            "
+        );
+
+        assert_eq!(Day5.part1(input), "143");
+        assert_eq!(Day5.part2(input), "123");
+    }
+}
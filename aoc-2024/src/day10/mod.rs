@@ -2,7 +2,7 @@ use std::collections::{HashMap, HashSet};
 
 use colored::{Colorize, CustomColor};
 use palette::{FromColor, Hsl, Srgb};
-use shared::Solution;
+use shared::{Point, Solution};
 
 fn hsl_to_color(hsl: Hsl) -> CustomColor {
     let rgb: Srgb<u8> = Srgb::from_color(hsl).into();
@@ -34,53 +34,6 @@ fn get_color(val: u8) -> CustomColor {
     }
 }
 
-/** (col, row) */
-type Coordinate = (usize, usize);
-
-trait Traversable
-where
-    Self: Sized,
-{
-    fn left(&self) -> Option<Self>;
-    fn right(&self) -> Option<Self>;
-    fn up(&self) -> Option<Self>;
-    fn down(&self) -> Option<Self>;
-}
-
-impl Traversable for Coordinate {
-    fn left(&self) -> Option<Coordinate> {
-        let (col, row) = self;
-
-        if *col == 0 {
-            return None;
-        }
-
-        Some((*col - 1, *row))
-    }
-
-    fn right(&self) -> Option<Coordinate> {
-        let (col, row) = self;
-
-        Some((*col + 1, *row))
-    }
-
-    fn up(&self) -> Option<Coordinate> {
-        let (col, row) = self;
-
-        if *row == 0 {
-            return None;
-        }
-
-        Some((*col, *row - 1))
-    }
-
-    fn down(&self) -> Option<Coordinate> {
-        let (col, row) = self;
-
-        Some((*col, *row + 1))
-    }
-}
-
 struct Problem {
     map: Vec<Vec<u8>>,
 }
@@ -109,31 +62,25 @@ impl Problem {
         });
     }
 
-    fn in_bounds(&self, position: Coordinate) -> bool {
-        let (col, row) = position;
-
-        if row >= self.map.len() || col >= self.map[0].len() {
-            return false;
-        }
+    fn get(&self, position: Point) -> Option<u8> {
+        let row = self.map.get(usize::try_from(position.y).ok()?)?;
 
-        true
+        row.get(usize::try_from(position.x).ok()?).copied()
     }
 
-    fn find_trails(&self) -> Vec<Vec<Coordinate>> {
+    fn find_trails(&self) -> Vec<Vec<Point>> {
         let mut trails = vec![];
 
         fn find_trails_inner(
-            current_trail: Vec<Coordinate>,
-            position: Coordinate,
+            current_trail: Vec<Point>,
+            position: Point,
             problem: &Problem,
-            trails: &mut Vec<Vec<Coordinate>>,
+            trails: &mut Vec<Vec<Point>>,
         ) {
-            let map = &problem.map;
-            let (col, row) = position;
-            let level = map[row][col];
+            let level = problem.get(position).unwrap();
 
             let next_level = if let Some(prev_position) = current_trail.last() {
-                let prev_level = map[prev_position.1][prev_position.0];
+                let prev_level = problem.get(*prev_position).unwrap();
                 prev_level + 1
             } else {
                 0
@@ -153,20 +100,14 @@ impl Problem {
             }
 
             // Continue the trail
-            [
-                position.up(),
-                position.down(),
-                position.left(),
-                position.right(),
-            ]
-            .iter()
-            .flatten()
-            .filter(|&next_position| problem.in_bounds(*next_position))
-            .for_each(|&next_position| {
-                let mut new_trail = current_trail.clone();
-                new_trail.push(position);
-                find_trails_inner(new_trail, next_position, problem, trails);
-            });
+            position
+                .neighbours()
+                .filter(|&next_position| problem.get(next_position).is_some())
+                .for_each(|next_position| {
+                    let mut new_trail = current_trail.clone();
+                    new_trail.push(position);
+                    find_trails_inner(new_trail, next_position, problem, trails);
+                });
         }
 
         for row in 0..self.map.len() {
@@ -177,7 +118,7 @@ impl Problem {
                     continue;
                 }
 
-                let position = (col, row);
+                let position = Point::new(col as i64, row as i64);
                 find_trails_inner(vec![], position, self, &mut trails);
             }
         }
@@ -186,7 +127,7 @@ impl Problem {
     }
 }
 
-fn score_trailheads(trails: &[Vec<Coordinate>]) -> u32 {
+fn score_trailheads(trails: &[Vec<Point>]) -> u32 {
     let mut freq_map = HashMap::new();
 
     trails.iter().for_each(|trail| {
@@ -203,7 +144,7 @@ fn score_trailheads(trails: &[Vec<Coordinate>]) -> u32 {
         .sum::<usize>() as u32
 }
 
-fn trailhead_ratings(trails: &[Vec<Coordinate>]) -> u32 {
+fn trailhead_ratings(trails: &[Vec<Point>]) -> u32 {
     let mut freq_map = HashMap::new();
 
     trails.iter().for_each(|trail| {
@@ -1,6 +1,4 @@
-use std::collections::HashMap;
-
-use shared::Solution;
+use shared::{Memo, Solution};
 
 pub struct Day11;
 
@@ -48,47 +46,35 @@ impl Problem {
     }
 
     fn blink_tree(&self, times: u8) -> u64 {
-        let mut memo = HashMap::new();
-
-        fn dfs(stone: u64, times: u8, memo: &mut HashMap<(u64, u8), u64>) -> u64 {
+        fn count_stones(stone: u64, times: u8, memo: &mut Memo<(u64, u8), u64>) -> u64 {
             if times == 0 {
                 return 1;
             }
 
-            let key = (stone, times);
-
-            if memo.contains_key(&key) {
-                return *memo.get(&key).unwrap();
-            }
-
-            let result = if stone == 0 {
-                // 0 replaced by 1
-                dfs(1, times - 1, memo)
-            } else if stone.to_string().len().is_multiple_of(2) {
-                // Even digits split
-                let digit_string = stone.to_string();
-                let length = digit_string.len();
-                let (first_half, second_half) = digit_string.split_at(length / 2);
-
-                dfs(first_half.parse().unwrap(), times - 1, memo)
-                    + dfs(second_half.parse().unwrap(), times - 1, memo)
-            } else {
-                // Other stones are multiplied by 2024
-                dfs(stone * 2024, times - 1, memo)
-            };
-
-            memo.insert(key, result);
-
-            result
+            memo.get_or_compute(&(stone, times), |memo| {
+                if stone == 0 {
+                    // 0 replaced by 1
+                    count_stones(1, times - 1, memo)
+                } else if stone.to_string().len().is_multiple_of(2) {
+                    // Even digits split
+                    let digit_string = stone.to_string();
+                    let length = digit_string.len();
+                    let (first_half, second_half) = digit_string.split_at(length / 2);
+
+                    count_stones(first_half.parse().unwrap(), times - 1, memo)
+                        + count_stones(second_half.parse().unwrap(), times - 1, memo)
+                } else {
+                    // Other stones are multiplied by 2024
+                    count_stones(stone * 2024, times - 1, memo)
+                }
+            })
         }
 
-        let mut i = 0;
+        let mut memo = Memo::new("day11 stones");
+
         self.stones
             .iter()
-            .map(|&stone| {
-                i += 1;
-                dfs(stone, times, &mut memo)
-            })
+            .map(|&stone| count_stones(stone, times, &mut memo))
             .sum()
     }
 }
@@ -110,3 +96,17 @@ impl Solution for Day11 {
         problem.blink_tree(75).to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day11() {
+        assert_eq!(Day11.part1("125 17"), "55312");
+
+        let problem = Problem::parse_input("125 17");
+
+        assert_eq!(problem.blink_tree(25), 55312);
+    }
+}
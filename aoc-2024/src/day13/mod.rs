@@ -1,44 +1,49 @@
 use regex::Regex;
 
-use shared::Solution;
+use shared::{
+    math::{solve_2x2, LinearSolutions},
+    Point, Solution, Vector,
+};
 
 pub struct Day13;
 
-#[derive(Debug, PartialEq)]
-struct Coordinate {
-    x: usize,
-    y: usize,
-}
-
-#[derive(Debug)]
-struct ClawMachineButton {
-    x: usize,
-    y: usize,
-}
-
 #[derive(Debug)]
 struct ClawMachine {
-    prize_location: Coordinate,
-    button_a: ClawMachineButton,
-    button_b: ClawMachineButton,
+    prize_location: Point,
+    button_a: Vector,
+    button_b: Vector,
 }
 
 impl ClawMachine {
-    fn fewest_tokens(&self) -> Option<u32> {
-        for b in 0..=100 {
-            for a in 0..=100 {
-                let result = Coordinate {
-                    x: self.button_a.x * a + self.button_b.x * b,
-                    y: self.button_a.y * a + self.button_b.y * b,
-                };
-
-                if result == self.prize_location {
-                    return Some(3 * a as u32 + b as u32);
-                }
+    /**
+     * Cheapest way to win, with A presses costing 3 tokens and B presses 1,
+     * pressing each button at most `max_presses` times if given
+     */
+    fn fewest_tokens(&self, prize_offset: i64, max_presses: Option<i64>) -> Option<i64> {
+        let solutions = solve_2x2(
+            [
+                [self.button_a.x, self.button_b.x],
+                [self.button_a.y, self.button_b.y],
+            ],
+            [
+                self.prize_location.x + prize_offset,
+                self.prize_location.y + prize_offset,
+            ],
+        );
+
+        match solutions {
+            LinearSolutions::None => None,
+            LinearSolutions::One(a, b) => {
+                let too_many = max_presses.is_some_and(|max| a > max || b > max);
+
+                (!too_many).then_some(3 * a + b)
+            }
+            // Both buttons move along the same line, so pick the cheapest mix
+            LinearSolutions::Many(family) => {
+                family.cheapest((3, 1), max_presses).map(|(a, b)| 3 * a + b)
             }
+            LinearSolutions::All => Some(0),
         }
-
-        None
     }
 }
 
@@ -63,18 +68,9 @@ impl Problem {
             let (_, [p_x, p_y]) = prize_regex.captures(prize_line).unwrap().extract();
 
             machines.push(ClawMachine {
-                button_a: ClawMachineButton {
-                    x: a_x.parse().unwrap(),
-                    y: a_y.parse().unwrap(),
-                },
-                button_b: ClawMachineButton {
-                    x: b_x.parse().unwrap(),
-                    y: b_y.parse().unwrap(),
-                },
-                prize_location: Coordinate {
-                    x: p_x.parse().unwrap(),
-                    y: p_y.parse().unwrap(),
-                },
+                button_a: Vector::new(a_x.parse().unwrap(), a_y.parse().unwrap()),
+                button_b: Vector::new(b_x.parse().unwrap(), b_y.parse().unwrap()),
+                prize_location: Point::new(p_x.parse().unwrap(), p_y.parse().unwrap()),
             });
         }
 
@@ -82,30 +78,6 @@ impl Problem {
     }
 }
 
-fn is_integer(x: f64) -> bool {
-    x.fract().abs() < f64::EPSILON
-}
-
-fn calculate_a(x: i64, xx: i64, y: i64, yy: i64, c: i64, d: i64) -> Option<u64> {
-    let result = (c * yy - d * xx) as f64 / (x * yy - y * xx) as f64;
-
-    if is_integer(result) {
-        Some(result as u64)
-    } else {
-        None
-    }
-}
-
-fn calculate_b(x: i64, xx: i64, y: i64, yy: i64, c: i64, d: i64) -> Option<u64> {
-    let result = (c * y - d * x) as f64 / (y * xx - x * yy) as f64;
-
-    if is_integer(result) {
-        Some(result as u64)
-    } else {
-        None
-    }
-}
-
 impl Solution for Day13 {
     fn part1(&self, input: &str) -> String {
         let problem = Problem::parse_input(input);
@@ -113,13 +85,12 @@ impl Solution for Day13 {
         problem
             .machines
             .iter()
-            .flat_map(|machine| machine.fewest_tokens())
-            .sum::<u32>()
+            .flat_map(|machine| machine.fewest_tokens(0, Some(100)))
+            .sum::<i64>()
             .to_string()
     }
 
     fn part2(&self, input: &str) -> String {
-        // Add 10000000000000 to the X and Y of each price coord
         const OFFSET: i64 = 10000000000000;
 
         let problem = Problem::parse_input(input);
@@ -127,25 +98,8 @@ impl Solution for Day13 {
         problem
             .machines
             .iter()
-            .flat_map(|machine| {
-                let x = machine.button_a.x as i64;
-                let xx = machine.button_b.x as i64;
-                let y = machine.button_a.y as i64;
-                let yy = machine.button_b.y as i64;
-                let c = machine.prize_location.x as i64 + OFFSET;
-                let d = machine.prize_location.y as i64 + OFFSET;
-
-                let a = calculate_a(x, xx, y, yy, c, d);
-                let b = calculate_b(x, xx, y, yy, c, d);
-
-                if let (Some(a), Some(b)) = (a, b) {
-                    let result = 3 * a + b;
-                    Some(result)
-                } else {
-                    None
-                }
-            })
-            .sum::<u64>()
+            .flat_map(|machine| machine.fewest_tokens(OFFSET, None))
+            .sum::<i64>()
             .to_string()
     }
 }
@@ -185,4 +139,71 @@ mod tests {
 
         assert_eq!(result, "0");
     }
+
+    #[test]
+    fn test_day13_collinear_buttons() {
+        let input = dedent!(
+            "
+            Button A: X+2, Y+2
+            Button B: X+1, Y+1
+            Prize: X=10, Y=10
+
+            Button A: X+4, Y+4
+            Button B: X+1, Y+1
+            Prize: X=10, Y=10
+
+            Button A: X+2, Y+4
+            Button B: X+1, Y+2
+            Prize: X=3, Y=5
+            "
+        );
+
+        // 10 B presses, then 2 A and 2 B presses, then no way to win
+        assert_eq!(Day13.part1(input), "18");
+    }
+
+    #[test]
+    fn test_day13_press_limit() {
+        let input = dedent!(
+            "
+            Button A: X+1, Y+2
+            Button B: X+2, Y+1
+            Prize: X=101, Y=202
+
+            Button A: X+2, Y+2
+            Button B: X+1, Y+1
+            Prize: X=250, Y=250
+            "
+        );
+
+        // The first machine needs 101 A presses, and the second can't use
+        // 250 B presses, so the cheapest it can do is 75 A and 100 B
+        assert_eq!(Day13.part1(input), "325");
+    }
+
+    #[test]
+    fn test_day13_full_example() {
+        let input = dedent!(
+            "
+            Button A: X+94, Y+34
+            Button B: X+22, Y+67
+            Prize: X=8400, Y=5400
+
+            Button A: X+26, Y+66
+            Button B: X+67, Y+21
+            Prize: X=12748, Y=12176
+
+            Button A: X+17, Y+86
+            Button B: X+84, Y+37
+            Prize: X=7870, Y=6450
+
+            Button A: X+69, Y+23
+            Button B: X+27, Y+71
+            Prize: X=18641, Y=10279
+            "
+        );
+
+        assert_eq!(Day13.part1(input), "480");
+        assert_eq!(Day13.part2(input), "875318608908");
+    }
 }
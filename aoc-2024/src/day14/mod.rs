@@ -2,36 +2,24 @@ use std::collections::HashMap;
 
 use regex::Regex;
 
-use shared::Solution;
+use shared::{cycle::find_cycle, Point, Solution, Torus, Vector};
 
 pub struct Day14;
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
-struct Coordinate {
-    x: u32,
-    y: u32,
-}
-
-#[derive(Debug)]
-struct Velocity {
-    x: i32,
-    y: i32,
-}
-
 #[derive(Debug)]
 struct Robot {
-    position: Coordinate,
-    velocity: Velocity,
+    position: Point,
+    velocity: Vector,
 }
 
 struct Map {
-    width: u32,
-    height: u32,
+    width: i64,
+    height: i64,
     robots: Vec<Robot>,
 }
 
 impl Map {
-    fn parse_input(width: u32, height: u32, input: &str) -> Map {
+    fn parse_input(width: i64, height: i64, input: &str) -> Map {
         let regex = Regex::new(r"p=(\d+),(\d+) v=(-?\d+),(-?\d+)").unwrap();
 
         let robots = input
@@ -41,14 +29,8 @@ impl Map {
                 let (_, [px, py, vx, vy]) = regex.captures(line).unwrap().extract();
 
                 Robot {
-                    position: Coordinate {
-                        x: px.parse().unwrap(),
-                        y: py.parse().unwrap(),
-                    },
-                    velocity: Velocity {
-                        x: vx.parse().unwrap(),
-                        y: vy.parse().unwrap(),
-                    },
+                    position: Point::new(px.parse().unwrap(), py.parse().unwrap()),
+                    velocity: Vector::new(vx.parse().unwrap(), vy.parse().unwrap()),
                 }
             })
             .collect();
@@ -60,30 +42,30 @@ impl Map {
         }
     }
 
-    fn simulate(&mut self) {
-        for robot in self.robots.iter_mut() {
-            let mut new_x = robot.position.x as i32 + robot.velocity.x;
-            let mut new_y = robot.position.y as i32 + robot.velocity.y;
-
-            if new_x < 0 {
-                new_x = self.width as i32 + (new_x % self.width as i32);
-            } else {
-                new_x %= self.width as i32;
-            }
-
-            if new_y < 0 {
-                new_y = self.height as i32 + (new_y % self.height as i32);
-            } else {
-                new_y %= self.height as i32;
-            }
+    /** Moves every robot forward by `seconds` in a single step */
+    fn simulate(&mut self, seconds: i64) {
+        let torus = Torus::new(self.width, self.height);
 
-            robot.position = Coordinate {
-                x: new_x as u32,
-                y: new_y as u32,
-            }
+        for robot in self.robots.iter_mut() {
+            robot.position = torus.step_n(robot.position, robot.velocity, seconds);
         }
     }
 
+    /** How many seconds until every robot is back where it started */
+    fn period(&self) -> usize {
+        let torus = Torus::new(self.width, self.height);
+        let positions = self.robots.iter().map(|robot| robot.position).collect();
+
+        find_cycle(positions, |positions: &Vec<Point>| {
+            positions
+                .iter()
+                .zip(&self.robots)
+                .map(|(&position, robot)| torus.step(position, robot.velocity))
+                .collect()
+        })
+        .length
+    }
+
     fn quadrants(&self) -> [Vec<&Robot>; 4] {
         let mut q1 = vec![];
         let mut q2 = vec![];
@@ -93,7 +75,7 @@ impl Map {
         let x_mid = self.width / 2;
         let y_mid = self.height / 2;
 
-        let mut robot_map: HashMap<Coordinate, Vec<&Robot>> = HashMap::new();
+        let mut robot_map: HashMap<Point, Vec<&Robot>> = HashMap::new();
         self.robots.iter().for_each(|robot| {
             robot_map
                 .entry(robot.position)
@@ -103,7 +85,7 @@ impl Map {
 
         for x in 0..x_mid {
             for y in 0..y_mid {
-                if let Some(robots) = robot_map.get(&Coordinate { x, y }) {
+                if let Some(robots) = robot_map.get(&Point::new(x, y)) {
                     robots.iter().for_each(|&robot| q1.push(robot));
                 }
             }
@@ -111,7 +93,7 @@ impl Map {
 
         for x in (x_mid + 1)..self.width {
             for y in 0..y_mid {
-                if let Some(robots) = robot_map.get(&Coordinate { x, y }) {
+                if let Some(robots) = robot_map.get(&Point::new(x, y)) {
                     robots.iter().for_each(|&robot| q2.push(robot));
                 }
             }
@@ -119,7 +101,7 @@ impl Map {
 
         for x in 0..x_mid {
             for y in (y_mid + 1)..self.height {
-                if let Some(robots) = robot_map.get(&Coordinate { x, y }) {
+                if let Some(robots) = robot_map.get(&Point::new(x, y)) {
                     robots.iter().for_each(|&robot| q3.push(robot));
                 }
             }
@@ -127,7 +109,7 @@ impl Map {
 
         for x in (x_mid + 1)..self.width {
             for y in (y_mid + 1)..self.height {
-                if let Some(robots) = robot_map.get(&Coordinate { x, y }) {
+                if let Some(robots) = robot_map.get(&Point::new(x, y)) {
                     robots.iter().for_each(|&robot| q4.push(robot));
                 }
             }
@@ -137,7 +119,7 @@ impl Map {
     }
 
     fn is_xmas_tree(&self) -> bool {
-        let mut robot_map: HashMap<Coordinate, Vec<&Robot>> = HashMap::new();
+        let mut robot_map: HashMap<Point, Vec<&Robot>> = HashMap::new();
         self.robots.iter().for_each(|robot| {
             robot_map
                 .entry(robot.position)
@@ -148,7 +130,7 @@ impl Map {
         for y in 0..self.height {
             let mut consecutive_count = 0;
             for x in 0..self.width {
-                if robot_map.contains_key(&Coordinate { x, y }) {
+                if robot_map.contains_key(&Point::new(x, y)) {
                     consecutive_count += 1;
                 } else {
                     consecutive_count = 0;
@@ -164,7 +146,7 @@ impl Map {
     }
 
     fn print(&self) {
-        let mut robot_map: HashMap<Coordinate, Vec<&Robot>> = HashMap::new();
+        let mut robot_map: HashMap<Point, Vec<&Robot>> = HashMap::new();
         self.robots.iter().for_each(|robot| {
             robot_map
                 .entry(robot.position)
@@ -174,7 +156,7 @@ impl Map {
 
         for y in 0..self.height {
             for x in 0..self.width {
-                let cell = match robot_map.get(&Coordinate { x, y }) {
+                let cell = match robot_map.get(&Point::new(x, y)) {
                     Some(robots) => robots.len().to_string(),
                     None => ".".to_string(),
                 };
@@ -190,10 +172,7 @@ impl Map {
 impl Solution for Day14 {
     fn part1(&self, input: &str) -> String {
         let mut map = Map::parse_input(101, 103, input);
-
-        for _ in 0..100 {
-            map.simulate();
-        }
+        map.simulate(100);
 
         map.quadrants()
             .iter()
@@ -205,19 +184,19 @@ impl Solution for Day14 {
 
     fn part2(&self, input: &str) -> String {
         let mut map = Map::parse_input(101, 103, input);
-        let mut result = -1;
 
-        for i in 1..10000 {
-            map.simulate();
+        // The robots repeat their layout after one period, so a tree that
+        // isn't found within it will never appear
+        for i in 1..=map.period() {
+            map.simulate(1);
 
             if map.is_xmas_tree() {
                 map.print();
-                result = i;
-                break;
+                return i.to_string();
             }
         }
 
-        result.to_string()
+        "-1".to_string()
     }
 }
 
@@ -246,10 +225,7 @@ mod tests {
             "
         );
         let mut map = Map::parse_input(11, 7, input);
-
-        for _ in 0..100 {
-            map.simulate();
-        }
+        map.simulate(100);
 
         map.print();
 
@@ -265,5 +241,6 @@ mod tests {
             .to_string();
 
         assert_eq!(result, "12");
+        assert_eq!(map.period(), 77);
     }
 }
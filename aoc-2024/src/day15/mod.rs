@@ -1,40 +1,11 @@
 use std::{borrow::BorrowMut, collections::HashMap};
 
-use shared::Solution;
+use shared::{Direction, FromByte, Point, Solution};
 
 pub struct Day15;
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
-struct Coordinate {
-    x: u32,
-    y: u32,
-}
-
-impl Coordinate {
-    fn gps_coordinate(&self) -> u32 {
-        100 * self.y + self.x
-    }
-
-    fn translate(&self, direction: Direction) -> Coordinate {
-        match direction {
-            Direction::Up => Coordinate {
-                x: self.x,
-                y: self.y.saturating_sub(1),
-            },
-            Direction::Down => Coordinate {
-                x: self.x,
-                y: self.y + 1,
-            },
-            Direction::Left => Coordinate {
-                x: self.x.saturating_sub(1),
-                y: self.y,
-            },
-            Direction::Right => Coordinate {
-                x: self.x + 1,
-                y: self.y,
-            },
-        }
-    }
+fn gps_coordinate(position: &Point) -> i64 {
+    100 * position.y + position.x
 }
 
 #[derive(Debug)]
@@ -46,18 +17,10 @@ enum Tile {
     Robot,
 }
 
-#[derive(Debug, Clone, Copy)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
 struct Map {
-    tiles: HashMap<Coordinate, Tile>,
+    tiles: HashMap<Point, Tile>,
     instructions: Vec<Direction>,
-    robot_position: Coordinate,
+    robot_position: Point,
 }
 
 impl Map {
@@ -85,10 +48,7 @@ impl Map {
             })
         {
             for (x, char) in line.chars().enumerate() {
-                let position = Coordinate {
-                    x: x as u32,
-                    y: y as u32,
-                };
+                let position = Point::new(x as i64, y as i64);
 
                 match char {
                     '#' => {
@@ -115,15 +75,7 @@ impl Map {
         let mut instructions = vec![];
 
         for line in lines {
-            for char in line.chars() {
-                match char {
-                    '^' => instructions.push(Direction::Up),
-                    'v' => instructions.push(Direction::Down),
-                    '<' => instructions.push(Direction::Left),
-                    '>' => instructions.push(Direction::Right),
-                    _ => {}
-                }
-            }
+            instructions.extend(line.bytes().filter_map(Direction::from_byte));
         }
 
         Map {
@@ -133,48 +85,46 @@ impl Map {
         }
     }
 
-    fn can_move(&mut self, current_position: Coordinate, direction: Direction) -> bool {
+    fn can_move(&mut self, current_position: Point, direction: Direction) -> bool {
         match self.tiles.get(&current_position) {
             Some(Tile::BoxLeft) => match direction {
                 Direction::Up | Direction::Down => {
-                    let box_right_position = current_position.translate(Direction::Right);
+                    let box_right_position = current_position.step(Direction::Right);
 
-                    return self.can_move(current_position.translate(direction), direction)
-                        && self.can_move(box_right_position.translate(direction), direction);
+                    return self.can_move(current_position.step(direction), direction)
+                        && self.can_move(box_right_position.step(direction), direction);
                 }
                 Direction::Left => {
-                    return self.can_move(current_position.translate(Direction::Left), direction);
+                    return self.can_move(current_position.step(Direction::Left), direction);
                 }
                 Direction::Right => {
                     return self.can_move(
                         current_position
-                            .translate(Direction::Right)
-                            .translate(Direction::Right),
+                            .step(Direction::Right)
+                            .step(Direction::Right),
                         direction,
                     )
                 }
             },
             Some(Tile::BoxRight) => match direction {
                 Direction::Up | Direction::Down => {
-                    let box_left_position = current_position.translate(Direction::Left);
+                    let box_left_position = current_position.step(Direction::Left);
 
-                    return self.can_move(box_left_position.translate(direction), direction)
-                        && self.can_move(current_position.translate(direction), direction);
+                    return self.can_move(box_left_position.step(direction), direction)
+                        && self.can_move(current_position.step(direction), direction);
                 }
                 Direction::Left => {
                     return self.can_move(
-                        current_position
-                            .translate(Direction::Left)
-                            .translate(Direction::Left),
+                        current_position.step(Direction::Left).step(Direction::Left),
                         direction,
                     );
                 }
                 Direction::Right => {
-                    return self.can_move(current_position.translate(Direction::Right), direction)
+                    return self.can_move(current_position.step(Direction::Right), direction)
                 }
             },
             Some(Tile::UnitBox) | Some(Tile::Robot) => {
-                return self.can_move(current_position.translate(direction), direction)
+                return self.can_move(current_position.step(direction), direction)
             }
             Some(Tile::Wall) => {
                 // Can't move walls
@@ -186,36 +136,36 @@ impl Map {
         true
     }
 
-    fn move_tile(&mut self, current_position: Coordinate, direction: Direction) {
+    fn move_tile(&mut self, current_position: Point, direction: Direction) {
         let can_move = self.can_move(current_position, direction);
 
         if !can_move {
             return;
         }
 
-        let next_position = current_position.translate(direction);
+        let next_position = current_position.step(direction);
 
         // Move tiles in the way first
         match self.tiles.get(&current_position) {
             Some(Tile::BoxLeft) => match direction {
                 Direction::Up | Direction::Down => {
                     self.move_tile(next_position, direction);
-                    self.move_tile(next_position.translate(Direction::Right), direction);
+                    self.move_tile(next_position.step(Direction::Right), direction);
                 }
                 Direction::Left => {
                     self.move_tile(next_position, direction);
                 }
                 Direction::Right => {
-                    self.move_tile(next_position.translate(Direction::Right), direction);
+                    self.move_tile(next_position.step(Direction::Right), direction);
                 }
             },
             Some(Tile::BoxRight) => match direction {
                 Direction::Up | Direction::Down => {
                     self.move_tile(next_position, direction);
-                    self.move_tile(next_position.translate(Direction::Left), direction);
+                    self.move_tile(next_position.step(Direction::Left), direction);
                 }
                 Direction::Left => {
-                    self.move_tile(next_position.translate(Direction::Left), direction);
+                    self.move_tile(next_position.step(Direction::Left), direction);
                 }
                 Direction::Right => {
                     self.move_tile(next_position, direction);
@@ -236,22 +186,20 @@ impl Map {
                 let removed_left_tile = self.tiles.remove(&current_position).unwrap();
                 let removed_right_tile = self
                     .tiles
-                    .remove(&current_position.translate(Direction::Right))
+                    .remove(&current_position.step(Direction::Right))
                     .unwrap();
                 self.tiles.insert(next_position, removed_left_tile);
-                self.tiles.insert(
-                    next_position.translate(Direction::Right),
-                    removed_right_tile,
-                );
+                self.tiles
+                    .insert(next_position.step(Direction::Right), removed_right_tile);
             }
             Some(Tile::BoxRight) => {
                 let removed_left_tile = self
                     .tiles
-                    .remove(&current_position.translate(Direction::Left))
+                    .remove(&current_position.step(Direction::Left))
                     .unwrap();
                 let removed_right_tile = self.tiles.remove(&current_position).unwrap();
                 self.tiles
-                    .insert(next_position.translate(Direction::Left), removed_left_tile);
+                    .insert(next_position.step(Direction::Left), removed_left_tile);
                 self.tiles.insert(next_position, removed_right_tile);
             }
             Some(Tile::UnitBox) => {
@@ -280,8 +228,8 @@ impl Solution for Day15 {
         map.tiles
             .iter()
             .filter(|(_, tile)| matches!(tile, Tile::UnitBox))
-            .map(|(coordinate, _)| coordinate.gps_coordinate())
-            .sum::<u32>()
+            .map(|(position, _)| gps_coordinate(position))
+            .sum::<i64>()
             .to_string()
     }
 
@@ -295,8 +243,8 @@ impl Solution for Day15 {
         map.tiles
             .iter()
             .filter(|(_, tile)| matches!(tile, Tile::BoxLeft))
-            .map(|(coordinate, _)| coordinate.gps_coordinate())
-            .sum::<u32>()
+            .map(|(position, _)| gps_coordinate(position))
+            .sum::<i64>()
             .to_string()
     }
 }
@@ -322,10 +270,10 @@ mod tests {
     }
 
     impl Map {
-        fn print(&self, width: u32, height: u32) {
+        fn print(&self, width: i64, height: i64) {
             for y in 0..height {
                 for x in 0..width {
-                    match self.tiles.get(&Coordinate { x, y }) {
+                    match self.tiles.get(&Point::new(x, y)) {
                         Some(tile) => print!("{}", tile.to_str()),
                         None => print!("."),
                     }
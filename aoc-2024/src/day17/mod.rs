@@ -1,9 +1,15 @@
+use std::fmt::{self, Display};
+
 use regex::Regex;
 
-use shared::Solution;
+use shared::{
+    vm::{Flow, Instruction as VmInstruction, Machine},
+    Solution,
+};
 
 pub struct Day17;
 
+#[derive(Debug, Clone, Copy)]
 enum Instruction {
     Adv(u8),
     Bxl(u8),
@@ -31,183 +37,263 @@ impl Instruction {
     }
 }
 
-struct Cpu {
-    register_a: u64,
-    register_b: u64,
-    register_c: u64,
-    instruction_pointer: u32,
-    program: Vec<u8>,
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Registers {
+    a: u64,
+    b: u64,
+    c: u64,
 }
 
-impl Cpu {
-    fn parse_input(input: &str) -> Cpu {
-        let mut lines = input.lines();
-        let register_regex = Regex::new(r"Register .: (\d+)").unwrap();
-
-        let register_a = lines
-            .next()
-            .map(|line| {
-                let (_, [value]) = register_regex.captures(line).unwrap().extract();
-
-                value.parse().unwrap()
-            })
-            .unwrap();
-        let register_b = lines
-            .next()
-            .map(|line| {
-                let (_, [value]) = register_regex.captures(line).unwrap().extract();
-
-                value.parse().unwrap()
-            })
-            .unwrap();
-        let register_c = lines
-            .next()
-            .map(|line| {
-                let (_, [value]) = register_regex.captures(line).unwrap().extract();
-
-                value.parse().unwrap()
-            })
-            .unwrap();
-
-        lines.next(); // Skip empty line
-
-        let mut program = vec![];
-
-        let mut iterator = lines.next().unwrap().chars().skip(9);
-        while let Some(value) = iterator.next() {
-            program.push(value.to_digit(10).unwrap() as u8);
-
-            iterator.next(); // Skip ','
-        }
-
-        Cpu {
-            register_a,
-            register_b,
-            register_c,
-            instruction_pointer: 0,
-            program,
+impl Registers {
+    fn combo_operand(&self, operand: u8) -> u64 {
+        match operand {
+            x if (0..=3).contains(&x) => x as u64,
+            4 => self.a,
+            5 => self.b,
+            6 => self.c,
+            invalid => panic!("Invalid combo operand {invalid}"),
         }
     }
 
-    fn execute(&mut self) -> String {
-        let mut output: Vec<String> = vec![];
+    /** Register A divided by two to the power of the combo operand */
+    fn divide(&self, operand: u8) -> u64 {
+        u32::try_from(self.combo_operand(operand))
+            .ok()
+            .and_then(|shift| self.a.checked_shr(shift))
+            .unwrap_or(0)
+    }
+}
 
-        while self.tick(&mut output) {}
+/** Mnemonic for a combo operand: the literals 0-3, or the register it reads */
+struct Combo(u8);
 
-        output.join(",")
+impl Display for Combo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            4 => write!(f, "a"),
+            5 => write!(f, "b"),
+            6 => write!(f, "c"),
+            literal => write!(f, "{literal}"),
+        }
     }
+}
 
-    fn tick(&mut self, output: &mut Vec<String>) -> bool {
-        if self.instruction_pointer >= (self.program.len() - 1) as u32 {
-            return false;
+impl Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Instruction::Adv(operand) => write!(f, "adv {}", Combo(operand)),
+            Instruction::Bxl(operand) => write!(f, "bxl {operand}"),
+            Instruction::Bst(operand) => write!(f, "bst {}", Combo(operand)),
+            Instruction::Jnz(operand) => write!(f, "jnz {operand}"),
+            Instruction::Bxc => write!(f, "bxc"),
+            Instruction::Out(operand) => write!(f, "out {}", Combo(operand)),
+            Instruction::Bdv(operand) => write!(f, "bdv {}", Combo(operand)),
+            Instruction::Cdv(operand) => write!(f, "cdv {}", Combo(operand)),
         }
+    }
+}
 
-        let instruction = {
-            let opcode = self.program[self.instruction_pointer as usize];
-            let operand = self.program[self.instruction_pointer as usize + 1];
-
-            Instruction::from_opcode(opcode, operand)
-        };
+impl VmInstruction for Instruction {
+    type State = Registers;
+    type Output = u8;
 
-        match instruction {
+    fn execute(&self, registers: &mut Registers, output: &mut Vec<u8>) -> Flow {
+        match *self {
             Instruction::Adv(operand) => {
-                let operand = self.compute_combo_operand(operand);
-                let numerator = self.register_a;
-                let denominator = 2_u64.pow(operand as u32);
-
-                self.register_a = numerator / denominator;
+                registers.a = registers.divide(operand);
             }
             Instruction::Bxl(operand) => {
-                self.register_b ^= operand as u64;
+                registers.b ^= operand as u64;
             }
             Instruction::Bst(operand) => {
-                let operand = self.compute_combo_operand(operand);
-                self.register_b = operand % 8;
+                registers.b = registers.combo_operand(operand) % 8;
             }
-            Instruction::Jnz(operand) if self.register_a != 0 => {
-                self.instruction_pointer = operand as u32;
-
-                // Skip incrementing the instruction pointer
-                return true;
+            Instruction::Jnz(operand) if registers.a != 0 => {
+                // Jump targets are byte offsets, each instruction is two bytes
+                return Flow::Jump(operand as usize / 2);
             }
+            Instruction::Jnz(_) => {}
             Instruction::Bxc => {
-                self.register_b ^= self.register_c;
+                registers.b ^= registers.c;
             }
             Instruction::Out(operand) => {
-                let operand = self.compute_combo_operand(operand);
-                output.push((operand % 8).to_string());
+                output.push((registers.combo_operand(operand) % 8) as u8);
             }
             Instruction::Bdv(operand) => {
-                let operand = self.compute_combo_operand(operand);
-                let numerator = self.register_a;
-                let denominator = 2_u64.pow(operand as u32);
-
-                self.register_b = numerator / denominator;
+                registers.b = registers.divide(operand);
             }
             Instruction::Cdv(operand) => {
-                let operand = self.compute_combo_operand(operand);
-                let numerator = self.register_a;
-                let denominator = 2_u64.pow(operand as u32);
-
-                self.register_c = numerator / denominator;
+                registers.c = registers.divide(operand);
             }
-            _ => {}
         }
 
-        self.instruction_pointer += 2;
+        Flow::Next
+    }
+}
 
-        true
+struct Cpu {
+    registers: Registers,
+    program: Vec<u8>,
+}
+
+impl Cpu {
+    fn parse_input(input: &str) -> Cpu {
+        let mut lines = input.lines();
+        let register_regex = Regex::new(r"Register .: (\d+)").unwrap();
+        let mut next_register = || {
+            let line = lines.next().unwrap();
+            let (_, [value]) = register_regex.captures(line).unwrap().extract();
+
+            value.parse().unwrap()
+        };
+
+        let registers = Registers {
+            a: next_register(),
+            b: next_register(),
+            c: next_register(),
+        };
+
+        lines.next(); // Skip empty line
+
+        let program = lines.next().unwrap()["Program: ".len()..]
+            .split(',')
+            .map(|value| value.trim().parse().unwrap())
+            .collect();
+
+        Cpu { registers, program }
     }
 
-    fn compute_combo_operand(&self, operand: u8) -> u64 {
-        match operand {
-            x if (0..=3).contains(&x) => x as u64,
-            4 => self.register_a,
-            5 => self.register_b,
-            6 => self.register_c,
-            invalid => panic!("Invalid combo operand {invalid}"),
+    fn machine(&self) -> Machine<Instruction> {
+        let instructions = self
+            .program
+            .chunks_exact(2)
+            .map(|pair| Instruction::from_opcode(pair[0], pair[1]))
+            .collect();
+
+        Machine::new(instructions, self.registers)
+    }
+
+    /** The lowest initial value of register A that makes the program output a copy of itself */
+    fn find_quine(&self) -> Option<u64> {
+        self.extend_quine(&mut self.machine(), 0, self.program.len())
+    }
+
+    /**
+     * Day 17 programs loop by shifting A right three bits until it reaches
+     * zero, printing a value derived from A's low bits each time round. So
+     * the last output depends only on A's most significant octal digit, the
+     * one before on the top two digits, and so on.
+     *
+     * Tries each next octal digit below `prefix` in ascending order, keeping
+     * those whose run outputs the program's last values from `unmatched`
+     * onwards and backtracking when none do. Once nothing is unmatched the
+     * run has output the whole program, which verifies the answer.
+     */
+    fn extend_quine(
+        &self,
+        machine: &mut Machine<Instruction>,
+        prefix: u64,
+        unmatched: usize,
+    ) -> Option<u64> {
+        if unmatched == 0 {
+            return Some(prefix);
         }
+
+        (0..8).find_map(|digit| {
+            let a = prefix << 3 | digit;
+
+            machine.reset(Registers {
+                a,
+                ..self.registers
+            });
+            machine.run();
+
+            if machine.output() == &self.program[unmatched - 1..] {
+                self.extend_quine(machine, a, unmatched - 1)
+            } else {
+                None
+            }
+        })
+    }
+}
+
+/** Runs the program, printing its disassembly and every instruction executed */
+pub fn trace(input: &str) {
+    let mut machine = Cpu::parse_input(input).machine();
+    machine.enable_trace();
+    machine.run();
+
+    println!("{}", machine.disassemble());
+    println!();
+
+    for entry in machine.trace() {
+        println!("{entry}");
     }
+
+    println!();
+    println!("Output: {}", join_output(machine.output()));
+}
+
+fn join_output(output: &[u8]) -> String {
+    output
+        .iter()
+        .map(|value| value.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
 }
 
 impl Solution for Day17 {
     fn part1(&self, input: &str) -> String {
-        let mut cpu = Cpu::parse_input(input);
+        let mut machine = Cpu::parse_input(input).machine();
+        machine.run();
 
-        cpu.execute()
+        join_output(machine.output())
     }
 
     fn part2(&self, input: &str) -> String {
-        let mut cpu = Cpu::parse_input(input);
-        let initial_register_b = cpu.register_b;
-        let initial_register_c = cpu.register_c;
-        let desired_output = cpu
-            .program
-            .iter()
-            .map(|value| value.to_string())
-            .collect::<Vec<_>>()
-            .join(",");
+        let cpu = Cpu::parse_input(input);
 
-        let mut candidate = 258394902691840;
+        cpu.find_quine()
+            .expect("No value of A makes the program output itself")
+            .to_string()
+    }
+}
 
-        loop {
-            cpu.instruction_pointer = 0;
-            cpu.register_a = candidate;
-            cpu.register_b = initial_register_b;
-            cpu.register_c = initial_register_c;
+#[cfg(test)]
+mod tests {
+    use dedent::dedent;
 
-            let output = cpu.execute();
+    use super::*;
 
-            // println!("{} <-- finding", desired_output);
-            // println!("{} <--{}", output, candidate);
+    #[test]
+    fn test_day17() {
+        let input = dedent!(
+            "
+            Register A: 729
+            Register B: 0
+            Register C: 0
 
-            if output == desired_output {
-                break;
-            }
+            Program: 0,1,5,4,3,0
+            "
+        );
+        let cpu = Cpu::parse_input(input.trim());
 
-            candidate += 1;
-        }
+        assert_eq!(cpu.machine().disassemble(), "adv 1; out a; jnz 0");
+        assert_eq!(Day17.part1(input.trim()), "4,6,3,5,6,3,5,2,1,0");
+    }
+
+    #[test]
+    fn test_day17_quine() {
+        let input = dedent!(
+            "
+            Register A: 2024
+            Register B: 0
+            Register C: 0
+
+            Program: 0,3,5,4,3,0
+            "
+        );
 
-        candidate.to_string()
+        assert_eq!(Day17.part2(input.trim()), "117440");
     }
 }
@@ -1,27 +1,20 @@
-use std::collections::{HashMap, HashSet};
-
-use binary_heap_plus::BinaryHeap;
-
-use shared::Solution;
+use shared::{bfs, Coordinate, DisjointSet, Grid, GridView, Solution};
 
 pub struct Day18;
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq, Eq)]
 enum Tile {
     Empty,
     Byte,
 }
 
-#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
-struct XYCoordinate(u32, u32);
-
 struct Map {
-    grid: Vec<Vec<Tile>>,
-    byte_locations: Vec<XYCoordinate>,
+    size: usize,
+    byte_locations: Vec<Coordinate>,
 }
 
 impl Map {
-    fn parse_input(input: &str) -> Map {
+    fn parse_input(input: &str, size: usize) -> Map {
         let byte_locations = input
             .trim()
             .lines()
@@ -30,82 +23,79 @@ impl Map {
                 let x = parts.next().unwrap().parse().unwrap();
                 let y = parts.next().unwrap().parse().unwrap();
 
-                XYCoordinate(x, y)
+                Coordinate::new(y, x)
             })
             .collect();
 
         Map {
-            grid: vec![vec![Tile::Empty; 71]; 71],
+            size,
             byte_locations,
         }
     }
 
-    fn simulate(&mut self, amount: usize) {
-        self.byte_locations
-            .iter()
-            .take(amount)
-            .for_each(|XYCoordinate(x, y)| {
-                self.grid[*y as usize][*x as usize] = Tile::Byte;
-            });
+    /** Steps from the top-left to the bottom-right once `byte_count` bytes have fallen */
+    fn exit_distance(&self, byte_count: usize) -> Option<usize> {
+        let mut grid = Grid::from_fn(self.size, self.size, |_| Tile::Empty);
+
+        for position in self.byte_locations.iter().take(byte_count) {
+            *grid.get_mut(position) = Tile::Byte;
+        }
+
+        let exit = Coordinate::new(self.size - 1, self.size - 1);
+
+        bfs(
+            [Coordinate::new(0, 0)],
+            |position| {
+                grid.neighbours(position)
+                    .into_iter()
+                    .filter(|neighbour| *grid.get(neighbour) == Tile::Empty)
+                    .collect::<Vec<_>>()
+            },
+            |position| *position == exit,
+        )
+        .cost()
     }
 
-    fn find_exit(&self) -> Option<Vec<XYCoordinate>> {
-        struct Candidate {
-            path: Vec<XYCoordinate>,
-            cost: u32,
+    /** The first byte that cuts off the exit, if any does */
+    fn first_blocking_byte(&self) -> Option<&Coordinate> {
+        // Start with every byte fallen and lift them off again in reverse,
+        // joining each freed cell to its free neighbours. The byte whose removal
+        // first connects the start to the exit is the one that cut it off
+        let mut first_fallen = Grid::from_fn(self.size, self.size, |_| None);
+
+        for (index, position) in self.byte_locations.iter().enumerate().rev() {
+            *first_fallen.get_mut(position) = Some(index);
         }
 
-        let mut position_costs = HashMap::new();
-        position_costs.insert(XYCoordinate(0, 0), 0);
-
-        let mut heap =
-            BinaryHeap::new_by(|a: &Candidate, b: &Candidate| a.cost.cmp(&b.cost).reverse());
-
-        heap.push(Candidate {
-            path: vec![XYCoordinate(0, 0)],
-            cost: 0,
-        });
-
-        while let Some(candidate) = heap.pop() {
-            let XYCoordinate(curr_x, curr_y) = *candidate.path.last().unwrap();
-            let next_cost = candidate.cost + 1;
-
-            for next_position in [
-                XYCoordinate(curr_x.saturating_sub(1), curr_y), // Left
-                XYCoordinate(curr_x + 1, curr_y),               // Right
-                XYCoordinate(curr_x, curr_y.saturating_sub(1)), // Up
-                XYCoordinate(curr_x, curr_y + 1),               // Down
-            ] {
-                if next_position.0 > 70 || next_position.1 > 70 {
-                    // Don't go off the map
-                    continue;
-                }
+        let start = first_fallen.index_of(&Coordinate::new(0, 0));
+        let exit = first_fallen.index_of(&Coordinate::new(self.size - 1, self.size - 1));
+        let mut components = DisjointSet::new(self.size * self.size);
 
-                match self.grid[next_position.1 as usize][next_position.0 as usize] {
-                    // Can't go through bytes
-                    Tile::Byte => continue,
-                    Tile::Empty => {}
-                }
+        for row in 0..self.size {
+            for col in 0..self.size {
+                let position = Coordinate::new(row, col);
 
-                // Don't explore places we can get to in less time
-                if let Some(cost) = position_costs.get(&next_position) {
-                    if *cost <= next_cost {
-                        continue;
-                    }
+                if first_fallen.get(&position).is_none() {
+                    join_free_neighbours(&mut components, &first_fallen, &position);
                 }
+            }
+        }
 
-                let mut next_path = candidate.path.clone();
-                next_path.push(next_position);
+        if components.connected(start, exit) {
+            return None;
+        }
 
-                if next_position == XYCoordinate(70, 70) {
-                    return Some(candidate.path);
-                }
+        for (index, position) in self.byte_locations.iter().enumerate().rev() {
+            // Later duplicates leave the cell blocked by an earlier byte
+            if *first_fallen.get(position) != Some(index) {
+                continue;
+            }
+
+            *first_fallen.get_mut(position) = None;
+            join_free_neighbours(&mut components, &first_fallen, position);
 
-                position_costs.insert(next_position, next_cost);
-                heap.push(Candidate {
-                    path: next_path,
-                    cost: next_cost,
-                })
+            if components.connected(start, exit) {
+                return Some(position);
             }
         }
 
@@ -113,51 +103,77 @@ impl Map {
     }
 }
 
+/** Merges `position` with each neighbour that no byte has fallen on */
+fn join_free_neighbours(
+    components: &mut DisjointSet,
+    first_fallen: &Grid<Option<usize>>,
+    position: &Coordinate,
+) {
+    for neighbour in first_fallen.neighbours(position) {
+        if first_fallen.get(&neighbour).is_none() {
+            components.union(
+                first_fallen.index_of(position),
+                first_fallen.index_of(&neighbour),
+            );
+        }
+    }
+}
+
 impl Solution for Day18 {
     fn part1(&self, input: &str) -> String {
-        let mut map = Map::parse_input(input);
-        map.simulate(1024);
-        let exit_path = map.find_exit().unwrap();
+        let map = Map::parse_input(input, 71);
 
-        exit_path.len().to_string()
+        map.exit_distance(1024).unwrap().to_string()
     }
 
     fn part2(&self, input: &str) -> String {
-        let mut byte_count = 1024;
-        let mut map = Map::parse_input(input);
-        map.simulate(byte_count);
-        let mut exit_path = map.find_exit();
-
-        while let Some(path) = &exit_path {
-            let mut path_coords = HashSet::new();
-            path.iter().for_each(|coordinate| {
-                path_coords.insert(*coordinate);
-            });
-
-            if let Some(index) = map
-                .byte_locations
-                .iter()
-                .enumerate()
-                .skip(byte_count)
-                .find_map(|(index, byte_position)| {
-                    if path_coords.contains(byte_position) {
-                        Some(index)
-                    } else {
-                        None
-                    }
-                })
-            {
-                map = Map::parse_input(input);
-                map.simulate(index + 1);
-                exit_path = map.find_exit();
-                byte_count = index + 1;
-            } else {
-                panic!("No byte found that would block exit path");
-            }
-        }
+        let map = Map::parse_input(input, 71);
+        let position = map.first_blocking_byte().unwrap();
 
-        let position = map.byte_locations[byte_count - 1];
+        format!("{},{}", position.x(), position.y())
+    }
+}
 
-        format!("{},{}", position.0, position.1)
+#[cfg(test)]
+mod tests {
+    use dedent::dedent;
+
+    use super::*;
+
+    #[test]
+    fn test_day18() {
+        let input = dedent!(
+            "
+            5,4
+            4,2
+            4,5
+            3,0
+            2,1
+            6,3
+            2,4
+            1,5
+            0,6
+            3,3
+            2,6
+            5,1
+            1,2
+            5,5
+            2,5
+            6,5
+            1,4
+            0,4
+            6,4
+            1,1
+            6,1
+            1,0
+            0,5
+            1,6
+            2,0
+            "
+        );
+        let map = Map::parse_input(input, 7);
+
+        assert_eq!(map.exit_distance(12), Some(22));
+        assert_eq!(map.first_blocking_byte(), Some(&Coordinate::new(1, 6)));
     }
 }
@@ -1,101 +1,20 @@
-use std::collections::HashMap;
-
-use shared::Solution;
+use shared::{Solution, Trie};
 
 pub struct Day19;
 
-#[derive(Debug)]
-enum TrieNode {
-    Root { children: HashMap<char, TrieNode> },
-    Value { children: HashMap<char, TrieNode> },
-    Terminal,
-}
-
-impl TrieNode {
-    fn from(values: &Vec<String>) -> TrieNode {
-        let mut root = TrieNode::Root {
-            children: HashMap::new(),
-        };
-
-        for value in values {
-            let mut current = &mut root;
-
-            for char in value.chars() {
-                let next = match current {
-                    TrieNode::Root { children } | TrieNode::Value { children, .. } => {
-                        children.entry(char).or_insert_with(|| TrieNode::Value {
-                            children: HashMap::new(),
-                        })
-                    }
-                    TrieNode::Terminal => break,
-                };
-
-                current = next;
-            }
-
-            if let TrieNode::Root { children } | TrieNode::Value { children, .. } = current {
-                children.insert('*', TrieNode::Terminal);
-            }
-        }
-
-        root
-    }
-
-    fn test(&self, input: &str, root: &TrieNode, solution_memo: &mut HashMap<String, u64>) -> u64 {
-        match self {
-            TrieNode::Root { children } => {
-                if solution_memo.contains_key(input) {
-                    return *solution_memo.get(input).unwrap();
-                }
-
-                let mut result = 0;
-                if let Some(next_char) = input.chars().next() {
-                    if let Some(child) = children.get(&next_char) {
-                        result += child.test(&input[1..], root, solution_memo);
-                    }
-                }
-
-                solution_memo.insert(input.to_string(), result);
-
-                result
-            }
-            TrieNode::Value { children, .. } => {
-                let mut result = 0;
-
-                if let Some(next_char) = input.chars().next() {
-                    if let Some(child) = children.get(&next_char) {
-                        result += child.test(&input[1..], root, solution_memo);
-                    }
-
-                    if children.contains_key(&'*') {
-                        result += root.test(input, root, solution_memo);
-                    }
-                } else if children.contains_key(&'*') {
-                    result += 1;
-                }
-
-                result
-            }
-            TrieNode::Terminal => 0,
-        }
-    }
-}
-
 struct Problem {
-    towels: Vec<String>,
+    towels: Trie<u8>,
     designs: Vec<String>,
 }
 
 impl Problem {
     fn parse_input(input: &str) -> Problem {
         let mut lines = input.trim().lines();
+        let mut towels = Trie::new();
 
-        let towels = lines
-            .next()
-            .unwrap()
-            .split(", ")
-            .map(|towel| towel.to_string())
-            .collect();
+        for towel in lines.next().unwrap().split(", ") {
+            towels.insert(towel.bytes());
+        }
 
         lines.next(); // Skip empty line
 
@@ -103,33 +22,33 @@ impl Problem {
 
         Problem { towels, designs }
     }
+
+    /** Ways each design can be made from the available towels */
+    fn arrangements(&self) -> impl Iterator<Item = u64> + '_ {
+        let mut buffer = vec![];
+
+        self.designs.iter().map(move |design| {
+            self.towels
+                .count_decompositions(design.as_bytes(), &mut buffer)
+        })
+    }
 }
 
 impl Solution for Day19 {
     fn part1(&self, input: &str) -> String {
         let problem = Problem::parse_input(input);
-        let trie = TrieNode::from(&problem.towels);
-        let mut solution_memo = HashMap::new();
 
         problem
-            .designs
-            .iter()
-            .filter(|design| trie.test(design, &trie, &mut solution_memo) > 0)
+            .arrangements()
+            .filter(|&count| count > 0)
             .count()
             .to_string()
     }
 
     fn part2(&self, input: &str) -> String {
         let problem = Problem::parse_input(input);
-        let trie = TrieNode::from(&problem.towels);
-        let mut solution_memo = HashMap::new();
 
-        problem
-            .designs
-            .iter()
-            .map(|design| trie.test(design, &trie, &mut solution_memo))
-            .sum::<u64>()
-            .to_string()
+        problem.arrangements().sum::<u64>().to_string()
     }
 }
 
@@ -153,4 +72,25 @@ mod tests {
 
         assert_eq!(result, "2");
     }
+
+    #[test]
+    fn test_day19_example() {
+        let input = dedent!(
+            "
+            r, wr, b, g, bwu, rb, gb, br
+
+            brwrr
+            bggr
+            gbbr
+            rrbgbr
+            ubwu
+            bwurrg
+            brgr
+            bbrwb
+            "
+        );
+
+        assert_eq!(Day19.part1(input), "6");
+        assert_eq!(Day19.part2(input), "16");
+    }
 }
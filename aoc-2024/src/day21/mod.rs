@@ -1,8 +1,6 @@
 use std::collections::HashMap;
 
-use itertools::Itertools;
-
-use shared::Solution;
+use shared::{Memo, Point, Solution};
 
 pub struct Day21;
 
@@ -68,29 +66,20 @@ impl Key {
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
-struct XYCoordinate(u8, u8);
-
-impl XYCoordinate {
-    fn sub(&self, rhs: &XYCoordinate) -> (i8, i8) {
-        (self.0 as i8 - rhs.0 as i8, self.1 as i8 - rhs.1 as i8)
-    }
-}
-
 #[derive(Clone)]
 struct Keypad {
-    /** (from, to) -> possible input sequences for move */
+    /** (from, to) -> possible input sequences for move, each ending in the press of Activate */
     input_sequences: HashMap<(Key, Key), Vec<Vec<Key>>>,
 }
 
 impl Keypad {
-    fn from(blank_space: XYCoordinate, buttons: Vec<(Key, XYCoordinate)>) -> Keypad {
+    fn from(blank_space: Point, buttons: Vec<(Key, Point)>) -> Keypad {
         let mut input_sequences = HashMap::new();
 
         for (from_key, from_pos) in buttons.iter() {
             for (to_key, to_pos) in buttons.iter() {
                 let mut sequences = vec![];
-                let (dx, dy) = to_pos.sub(from_pos);
+                let Point { x: dx, y: dy } = *to_pos - *from_pos;
 
                 let move_vertically = |result: &mut Vec<Key>| {
                     for _ in 0..dy.abs() {
@@ -104,10 +93,9 @@ impl Keypad {
                     }
                 };
 
-                let horizontal_clash =
-                    from_pos.1 == blank_space.1 && (from_pos.0 as i8 + dx) as u8 == blank_space.0;
-                let vertical_clash =
-                    from_pos.0 == blank_space.0 && (from_pos.1 as i8 + dy) as u8 == blank_space.1;
+                // Moving along one axis first mustn't pass over the gap in the keypad
+                let horizontal_clash = Point::new(to_pos.x, from_pos.y) == blank_space;
+                let vertical_clash = Point::new(from_pos.x, to_pos.y) == blank_space;
 
                 if !horizontal_clash {
                     let mut horizontal_first_sequence = vec![];
@@ -123,6 +111,10 @@ impl Keypad {
                     sequences.push(vertical_first_sequence);
                 }
 
+                for sequence in sequences.iter_mut() {
+                    sequence.push(Key::Activate);
+                }
+
                 input_sequences.insert((from_key.clone(), to_key.clone()), sequences);
             }
         }
@@ -150,13 +142,13 @@ impl Problem {
         // | < | v | > |
         // +---+---+---+
         let directional_keypad = Keypad::from(
-            XYCoordinate(0, 0),
+            Point::new(0, 0),
             vec![
-                (Key::Up, XYCoordinate(1, 0)),
-                (Key::Activate, XYCoordinate(2, 0)),
-                (Key::Left, XYCoordinate(0, 1)),
-                (Key::Down, XYCoordinate(1, 1)),
-                (Key::Right, XYCoordinate(2, 1)),
+                (Key::Up, Point::new(1, 0)),
+                (Key::Activate, Point::new(2, 0)),
+                (Key::Left, Point::new(0, 1)),
+                (Key::Down, Point::new(1, 1)),
+                (Key::Right, Point::new(2, 1)),
             ],
         );
 
@@ -170,19 +162,19 @@ impl Problem {
         //     | 0 | A |
         //     +---+---+
         let numeric_keypad = Keypad::from(
-            XYCoordinate(0, 3),
+            Point::new(0, 3),
             vec![
-                (Key::Seven, XYCoordinate(0, 0)),
-                (Key::Eight, XYCoordinate(1, 0)),
-                (Key::Nine, XYCoordinate(2, 0)),
-                (Key::Four, XYCoordinate(0, 1)),
-                (Key::Five, XYCoordinate(1, 1)),
-                (Key::Six, XYCoordinate(2, 1)),
-                (Key::One, XYCoordinate(0, 2)),
-                (Key::Two, XYCoordinate(1, 2)),
-                (Key::Three, XYCoordinate(2, 2)),
-                (Key::Zero, XYCoordinate(1, 3)),
-                (Key::Activate, XYCoordinate(2, 3)),
+                (Key::Seven, Point::new(0, 0)),
+                (Key::Eight, Point::new(1, 0)),
+                (Key::Nine, Point::new(2, 0)),
+                (Key::Four, Point::new(0, 1)),
+                (Key::Five, Point::new(1, 1)),
+                (Key::Six, Point::new(2, 1)),
+                (Key::One, Point::new(0, 2)),
+                (Key::Two, Point::new(1, 2)),
+                (Key::Three, Point::new(2, 2)),
+                (Key::Zero, Point::new(1, 3)),
+                (Key::Activate, Point::new(2, 3)),
             ],
         );
 
@@ -195,73 +187,70 @@ impl Problem {
         Problem { keypads, codes }
     }
 
+    /** One memo per keypad, so sequences can be looked up by slice */
+    fn memos(&self) -> Vec<Memo<Vec<Key>, usize>> {
+        self.keypads
+            .iter()
+            .map(|_| Memo::new("day21 sequences"))
+            .collect()
+    }
+
+    /**
+     * Presses needed on the outermost keypad to type `result_sequence` at `depth`,
+     * where `memos` holds the memos for this keypad and the ones outside it
+     */
     fn get_input_sequence(
         &self,
-        memo: &mut HashMap<usize, HashMap<String, usize>>,
+        memos: &mut [Memo<Vec<Key>, usize>],
         depth: usize,
-        result_sequence: Vec<Key>,
+        result_sequence: &[Key],
     ) -> usize {
-        let result_sequence_string = result_sequence.iter().map(|key| key.to_string()).join("");
-
-        if depth == self.keypads.len() {
-            return result_sequence_string.len();
-        }
-
-        if let Some(sequence_length) = memo
-            .get(&depth)
-            .and_then(|depth_memo| depth_memo.get(&result_sequence_string))
-        {
-            return *sequence_length;
-        }
-
-        let mut result = 0;
-
-        let keypad = &self.keypads[depth];
-        let mut from = &Key::Activate;
-        for to in result_sequence.iter() {
-            let sub_sequences = keypad
-                .input_sequences
-                .get(&(from.clone(), to.clone()))
-                .unwrap_or_else(|| {
-                    panic!(
-                        "from: {}, to: {}, depth: {}\n{:#?}",
-                        from.to_string(),
-                        to.to_string(),
-                        depth,
-                        keypad.input_sequences
-                    )
-                });
-
-            result += sub_sequences
-                .iter()
-                .cloned()
-                .map(|mut sub_sequence| {
-                    sub_sequence.push(Key::Activate);
-                    self.get_input_sequence(memo, depth + 1, sub_sequence)
-                })
-                .min()
-                .unwrap();
-
-            from = to;
-        }
-
-        memo.entry(depth)
-            .and_modify(|depth_memo| {
-                depth_memo.insert(result_sequence_string.clone(), result);
-            })
-            .or_insert_with(|| HashMap::from([(result_sequence_string, result)]));
+        let Some((memo, outer_memos)) = memos.split_first_mut() else {
+            return result_sequence.len();
+        };
+
+        memo.get_or_compute(result_sequence, |_| {
+            let keypad = &self.keypads[depth];
+            let mut result = 0;
+            let mut from = &Key::Activate;
+
+            for to in result_sequence.iter() {
+                let sub_sequences = keypad
+                    .input_sequences
+                    .get(&(from.clone(), to.clone()))
+                    .unwrap_or_else(|| {
+                        panic!(
+                            "from: {}, to: {}, depth: {}\n{:#?}",
+                            from.to_string(),
+                            to.to_string(),
+                            depth,
+                            keypad.input_sequences
+                        )
+                    });
+
+                result += sub_sequences
+                    .iter()
+                    .map(|sub_sequence| {
+                        self.get_input_sequence(outer_memos, depth + 1, sub_sequence)
+                    })
+                    .min()
+                    .unwrap();
+
+                from = to;
+            }
 
-        result
+            result
+        })
     }
 }
 
 impl Solution for Day21 {
     fn part1(&self, input: &str) -> String {
         let problem = Problem::parse_input(input, 2);
-        let mut memo = HashMap::new();
+        let mut memos = problem.memos();
         let mut result = 0;
 
-        for code in problem.codes.clone() {
+        for code in &problem.codes {
             let numeric_part = code
                 .iter()
                 .take(3)
@@ -270,7 +259,7 @@ impl Solution for Day21 {
                 .join("")
                 .parse::<u16>()
                 .unwrap();
-            let sequence_length = problem.get_input_sequence(&mut memo, 0, code);
+            let sequence_length = problem.get_input_sequence(&mut memos, 0, code);
 
             let complexity_score = numeric_part as usize * sequence_length;
             result += complexity_score;
@@ -281,10 +270,10 @@ impl Solution for Day21 {
 
     fn part2(&self, input: &str) -> String {
         let problem = Problem::parse_input(input, 25);
-        let mut memo = HashMap::new();
+        let mut memos = problem.memos();
         let mut result = 0;
 
-        for code in problem.codes.clone() {
+        for code in &problem.codes {
             let numeric_part = code
                 .iter()
                 .take(3)
@@ -293,7 +282,7 @@ impl Solution for Day21 {
                 .join("")
                 .parse::<u16>()
                 .unwrap();
-            let sequence_length = problem.get_input_sequence(&mut memo, 0, code);
+            let sequence_length = problem.get_input_sequence(&mut memos, 0, code);
 
             let complexity_score = numeric_part as usize * sequence_length;
             result += complexity_score;
@@ -1,203 +1,75 @@
-use std::{
-    collections::{HashMap, HashSet},
-    hash::Hash,
-};
-
 use itertools::Itertools;
 use regex::Regex;
 
-use shared::Solution;
+use shared::{Dot, Graph, Interner, Solution};
 
 pub struct Day23;
 
-type NodeId = String;
-
-#[derive(Debug)]
-struct Node {
-    id: NodeId,
-    neighbors: HashSet<NodeId>,
-}
-
-impl Hash for Node {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.id.hash(state);
-    }
-}
-
-impl PartialEq for Node {
-    fn eq(&self, other: &Self) -> bool {
-        self.id.eq(&other.id)
-    }
+struct Network {
+    names: Interner,
+    graph: Graph<usize>,
 }
 
-impl Eq for Node {}
+fn parse_input(input: &str) -> Network {
+    let mut names = Interner::new();
+    let mut graph = Graph::new();
+    let regex = Regex::new("(..)-(..)").unwrap();
 
-#[derive(Debug)]
-struct NodeGroup {
-    node_ids: Vec<NodeId>,
-}
+    for line in input.trim().lines() {
+        let (_, [node1_id, node2_id]) = regex.captures(line).unwrap().extract();
 
-impl NodeGroup {
-    fn size(&self) -> usize {
-        self.node_ids.len()
+        graph.add_undirected_edge(names.intern(node1_id), names.intern(node2_id));
     }
-}
 
-impl Hash for NodeGroup {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.node_ids.iter().sorted().join(":").hash(state);
-    }
+    Network { names, graph }
 }
 
-impl PartialEq for NodeGroup {
-    fn eq(&self, other: &Self) -> bool {
-        self.node_ids.iter().sorted().join(":") == other.node_ids.iter().sorted().join(":")
-    }
-}
+impl Network {
+    /** The LAN as an undirected DOT graph, highlighting the largest set of interconnected computers */
+    fn to_dot(&self) -> Dot {
+        let mut dot = self
+            .graph
+            .to_undirected_dot("lan", |&id| self.names.name(id).to_string());
+        let party = self.graph.maximum_clique();
 
-impl Eq for NodeGroup {}
-
-struct Graph {
-    nodes: HashMap<String, Node>,
-}
-
-impl Graph {
-    fn parse_input(input: &str) -> Graph {
-        let mut nodes = HashMap::new();
-        let regex = Regex::new("(..)-(..)").unwrap();
-
-        for line in input.trim().lines() {
-            let (_, [node1_id, node2_id]) = regex.captures(line).unwrap().extract();
-
-            nodes
-                .entry(node1_id.to_string())
-                .and_modify(|node: &mut Node| {
-                    node.neighbors.insert(node2_id.to_string());
-                })
-                .or_insert(Node {
-                    id: node1_id.to_string(),
-                    neighbors: HashSet::from([node2_id.to_string()]),
-                });
-
-            nodes
-                .entry(node2_id.to_string())
-                .and_modify(|node: &mut Node| {
-                    node.neighbors.insert(node1_id.to_string());
-                })
-                .or_insert(Node {
-                    id: node2_id.to_string(),
-                    neighbors: HashSet::from([node1_id.to_string()]),
-                });
-        }
-
-        Graph { nodes }
-    }
+        for (index, &a) in party.iter().enumerate() {
+            dot.highlight_node(self.names.name(a));
 
-    fn find_groups(&self) -> Vec<NodeGroup> {
-        let mut groups = HashSet::new();
-
-        for node in self.nodes.values() {
-            let neighbors = &node.neighbors;
-
-            let mut connectivity_map: HashMap<usize, Vec<&Node>> = HashMap::new();
-
-            for neighbor in neighbors.iter().map(|id| self.nodes.get(id).unwrap()) {
-                let number_of_shared_neighbors = neighbor
-                    .neighbors
-                    .iter()
-                    .filter(|&id| *id == node.id || neighbors.contains(id))
-                    .count();
-
-                connectivity_map
-                    .entry(number_of_shared_neighbors)
-                    .and_modify(|entry| entry.push(neighbor))
-                    .or_insert(vec![neighbor]);
-            }
-
-            for (_, neighbor_nodes) in connectivity_map {
-                let mut neighbor_nodes = neighbor_nodes;
-                while let Some(neighbor) = neighbor_nodes.pop() {
-                    let mut remaining_neighbor_nodes = vec![];
-                    let neighbors = neighbor_nodes
-                        .iter()
-                        .filter_map(|&other| {
-                            if neighbor.neighbors.contains(&other.id) {
-                                Some(other.id.clone())
-                            } else {
-                                remaining_neighbor_nodes.push(other);
-                                None
-                            }
-                        })
-                        .collect_vec();
-
-                    groups.insert(NodeGroup {
-                        node_ids: {
-                            let mut ids = vec![node.id.clone(), neighbor.id.clone()];
-                            ids.extend(neighbors);
-                            ids
-                        },
-                    });
-
-                    neighbor_nodes = remaining_neighbor_nodes;
-                }
+            for &b in &party[index + 1..] {
+                dot.highlight_edge(self.names.name(a), self.names.name(b));
             }
         }
 
-        groups.into_iter().collect()
+        dot
     }
 }
 
+/** The DOT graph written by `aoc export-graph` */
+pub fn export_graph(input: &str) -> Dot {
+    parse_input(input).to_dot()
+}
+
 impl Solution for Day23 {
     fn part1(&self, input: &str) -> String {
-        let graph = Graph::parse_input(input);
-        let mut result = HashSet::new();
-
-        for group in graph
-            .find_groups()
-            .into_iter()
-            .filter(|group| group.size() >= 3)
-        {
-            let mut sorted_ids = group
-                .node_ids
-                .iter()
-                .filter(|id| id.starts_with("t"))
-                .collect_vec();
-            sorted_ids.extend(group.node_ids.iter().filter(|id| !id.starts_with("t")));
-
-            for x in 0..=sorted_ids.len() {
-                let first_id = sorted_ids[x];
-
-                if !first_id.starts_with("t") {
-                    break;
-                }
-
-                for y in (x + 1)..sorted_ids.len() {
-                    let second_id = sorted_ids[y];
-                    for &third_id in sorted_ids.iter().skip(y + 1) {
-                        result.insert(NodeGroup {
-                            node_ids: vec![first_id.clone(), second_id.clone(), third_id.clone()],
-                        });
-                    }
-                }
-            }
-        }
-
-        result.len().to_string()
+        let Network { names, graph } = parse_input(input);
+
+        graph
+            .triangles()
+            .iter()
+            .filter(|triangle| triangle.iter().any(|&id| names.name(id).starts_with("t")))
+            .count()
+            .to_string()
     }
 
     fn part2(&self, input: &str) -> String {
-        let graph = Graph::parse_input(input);
-
-        let groups = graph.find_groups();
-        let mut largest_group = &groups[0];
+        let Network { names, graph } = parse_input(input);
 
-        groups.iter().skip(1).for_each(|group| {
-            if group.size() > largest_group.size() {
-                largest_group = group;
-            }
-        });
-
-        largest_group.node_ids.iter().sorted().join(",")
+        graph
+            .maximum_clique()
+            .into_iter()
+            .map(|id| names.name(id))
+            .sorted()
+            .join(",")
     }
 }
 
@@ -211,20 +83,46 @@ mod tests {
     fn test_day23() {
         let input = dedent!(
             "
-            t1-t2
-            t1-t3
-            t1-aa
-            t1-bb
-            t2-t3
-            t2-aa
-            t2-bb
-            t3-aa
-            t3-bb
-            aa-bb
+            kh-tc
+            qp-kh
+            de-cg
+            ka-co
+            yn-aq
+            qp-ub
+            cg-tb
+            vc-aq
+            tb-ka
+            wh-tc
+            yn-cg
+            kh-ub
+            ta-co
+            de-co
+            tc-td
+            tb-wq
+            wh-td
+            ta-ka
+            td-qp
+            aq-cg
+            wq-ub
+            ub-vc
+            de-ta
+            wq-aq
+            wq-vc
+            wh-yn
+            ka-de
+            kh-ta
+            co-tc
+            wh-qp
+            tb-vc
+            td-yn
             "
         );
-        let result = Day23.part1(input);
 
-        assert_eq!(result, "2");
+        assert_eq!(Day23.part1(input), "7");
+        assert_eq!(Day23.part2(input), "co,de,ka,ta");
+
+        // The four-computer party is joined by six highlighted links
+        let dot = parse_input(input).to_dot().to_string();
+        assert_eq!(dot.matches("penwidth").count(), 6);
     }
 }
@@ -1,12 +1,14 @@
 use std::{
-    collections::VecDeque,
+    collections::HashMap,
+    error::Error,
+    fmt::{self, Display},
     ops::{BitAnd, BitOr, BitXor},
 };
 
 use itertools::Itertools;
 use regex::Regex;
 
-use shared::Solution;
+use shared::{Dot, Graph, Interner, Solution, XorShift};
 
 pub struct Day24;
 
@@ -69,13 +71,7 @@ impl WireState {
     }
 }
 
-struct Wire {
-    name: String,
-    state: Option<WireState>,
-    dependency_indices: Vec<usize>,
-}
-
-#[derive(PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum GateType {
     And,
     Or,
@@ -91,14 +87,6 @@ impl GateType {
             unknown => panic!("Unknown gate type: {unknown}"),
         }
     }
-
-    fn to_str(&self) -> &str {
-        match self {
-            GateType::And => "AND",
-            GateType::Or => "OR",
-            GateType::Xor => "XOR",
-        }
-    }
 }
 
 struct LogicGate {
@@ -117,9 +105,33 @@ impl LogicGate {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum CircuitError {
+    /** Output wires of gates that feed back into each other */
+    Cycle(Vec<String>),
+    /** A gate input that no gate drives and that isn't given a value */
+    UndrivenWire(String),
+}
+
+impl Display for CircuitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CircuitError::Cycle(wires) => {
+                write!(f, "gates form a cycle through {}", wires.join(", "))
+            }
+            CircuitError::UndrivenWire(wire) => write!(f, "wire {wire} is never driven"),
+        }
+    }
+}
+
+impl Error for CircuitError {}
+
+/** Value of each wire, indexed by wire, or `None` for wires that weren't evaluated */
+type WireStates = Vec<Option<WireState>>;
+
 struct Circuit {
+    wire_names: Interner,
     initial_wire_states: Vec<(usize, WireState)>,
-    wires: Vec<Wire>,
     logic_gates: Vec<LogicGate>,
 }
 
@@ -128,8 +140,8 @@ impl Circuit {
         let mut lines = input.trim().lines();
 
         let mut circuit = Circuit {
+            wire_names: Interner::new(),
             initial_wire_states: vec![],
-            wires: vec![],
             logic_gates: vec![],
         };
 
@@ -147,16 +159,11 @@ impl Circuit {
             let (_, [wire_name, initial_state]) =
                 wire_state_regex.captures(line).unwrap().extract();
 
-            let wire = Wire {
-                name: wire_name.to_string(),
-                state: None,
-                dependency_indices: vec![],
-            };
+            let wire_index = circuit.wire_names.intern(wire_name);
 
             circuit
                 .initial_wire_states
-                .push((circuit.wires.len(), WireState::from_str(initial_state)));
-            circuit.wires.push(wire);
+                .push((wire_index, WireState::from_str(initial_state)));
         }
 
         for line in lines {
@@ -166,19 +173,12 @@ impl Circuit {
             let gate = LogicGate {
                 kind: GateType::from_str(gate),
                 inputs_indices: [
-                    circuit.get_or_insert_wire_index(wire_one),
-                    circuit.get_or_insert_wire_index(wire_two),
+                    circuit.wire_names.intern(wire_one),
+                    circuit.wire_names.intern(wire_two),
                 ],
-                output_index: circuit.get_or_insert_wire_index(output),
+                output_index: circuit.wire_names.intern(output),
             };
 
-            circuit.wires[gate.inputs_indices[0]]
-                .dependency_indices
-                .push(circuit.logic_gates.len());
-            circuit.wires[gate.inputs_indices[1]]
-                .dependency_indices
-                .push(circuit.logic_gates.len());
-
             circuit.logic_gates.push(gate);
         }
 
@@ -186,212 +186,517 @@ impl Circuit {
     }
 
     fn get_wire_index(&self, name: &str) -> Option<usize> {
-        self.wires.iter().position(|wire| wire.name == name)
+        self.wire_names.get(name)
     }
 
-    fn get_or_insert_wire_index(&mut self, name: &str) -> usize {
-        if let Some(index) = self.get_wire_index(name) {
-            return index;
-        }
+    fn wire_name(&self, index: usize) -> &str {
+        self.wire_names.name(index)
+    }
 
-        let new_index = self.wires.len();
-        self.wires.push(Wire {
-            name: name.to_string(),
-            state: None,
-            dependency_indices: vec![],
-        });
+    /** Gate indices ordered so that every gate comes after the gates driving its inputs */
+    fn evaluation_order(&self) -> Result<Vec<usize>, CircuitError> {
+        let mut drivers = vec![None; self.wire_names.len()];
 
-        new_index
-    }
+        for (gate_index, gate) in self.logic_gates.iter().enumerate() {
+            drivers[gate.output_index] = Some(gate_index);
+        }
 
-    fn simulate(&mut self) {
-        let mut update_queue: VecDeque<(usize, WireState)> = self
-            .initial_wire_states
-            .iter()
-            .map(|update| (update.0, update.1.clone()))
-            .collect();
-
-        while let Some((wire_index, state)) = update_queue.pop_front() {
-            let dependency_indices = {
-                let wire = &mut self.wires[wire_index];
-                wire.state = Some(state);
-                wire.dependency_indices.iter().cloned().collect_vec()
-            };
+        let mut graph = Graph::new();
 
-            for gate_index in dependency_indices {
-                let gate = &self.logic_gates[gate_index];
-                let wire_1 = &self.wires[gate.inputs_indices[0]];
-                let wire_2 = &self.wires[gate.inputs_indices[1]];
+        for (gate_index, gate) in self.logic_gates.iter().enumerate() {
+            graph.add_node(gate_index);
 
-                if let (Some(input_1), Some(input_2)) = (&wire_1.state, &wire_2.state) {
-                    let result = gate.compute(input_1, input_2);
-                    update_queue.push_back((gate.output_index, result));
+            for input_index in gate.inputs_indices {
+                if let Some(driver) = drivers[input_index] {
+                    graph.add_edge(driver, gate_index);
                 }
             }
         }
+
+        graph.topological_sort().ok_or_else(|| {
+            let cycle = graph
+                .strongly_connected_components()
+                .into_iter()
+                .find(|component| {
+                    component.len() > 1 || graph.has_edge(&component[0], &component[0])
+                })
+                .unwrap();
+
+            CircuitError::Cycle(
+                cycle
+                    .iter()
+                    .map(|&gate_index| self.wire_name(self.logic_gates[gate_index].output_index))
+                    .sorted()
+                    .map(str::to_string)
+                    .collect(),
+            )
+        })
     }
 
-    fn print_wire(&self, wire_name: &str, depth: usize) {
-        print!("{}", "     |".repeat(depth));
+    /** Evaluates every gate in dependency order, starting from the given input wire values */
+    fn evaluate(&self, inputs: &[(usize, WireState)]) -> Result<WireStates, CircuitError> {
+        let mut states = vec![None; self.wire_names.len()];
 
-        if depth >= 6 {
-            println!("...");
-            return;
+        for (wire_index, state) in inputs {
+            states[*wire_index] = Some(state.clone());
         }
 
-        if ['x', 'y'].contains(&wire_name.chars().nth(0).unwrap()) {
-            println!("- {wire_name}");
-        } else {
-            let wire_index = self.get_wire_index(wire_name).unwrap();
-            let parent_gate = self
-                .logic_gates
-                .iter()
-                .find(|gate| gate.output_index == wire_index)
-                .unwrap();
+        for gate_index in self.evaluation_order()? {
+            let gate = &self.logic_gates[gate_index];
+            let [input_1, input_2] = gate.inputs_indices.map(|input_index| {
+                states[input_index].clone().ok_or_else(|| {
+                    CircuitError::UndrivenWire(self.wire_name(input_index).to_string())
+                })
+            });
 
-            println!("{} ←[{}]", wire_name, parent_gate.kind.to_str());
-
-            let input_1 = parent_gate.inputs_indices[0];
-            let input_2 = parent_gate.inputs_indices[1];
-            self.print_wire(&self.wires[input_1].name, depth + 1);
-            self.print_wire(&self.wires[input_2].name, depth + 1);
+            states[gate.output_index] = Some(gate.compute(&input_1?, &input_2?));
         }
+
+        Ok(states)
     }
 
-    fn get_parent_gate(&self, wire_name: &str) -> &LogicGate {
-        let wire_index = self.get_wire_index(wire_name).unwrap();
+    /** The bits of the wires named `prefix` followed by a bit number, read as an integer */
+    fn read_number(&self, states: &WireStates, prefix: char) -> u64 {
+        self.wire_names
+            .iter()
+            .filter_map(|(index, name)| {
+                let bit = name.strip_prefix(prefix)?.parse::<u32>().ok()?;
 
-        self.logic_gates
+                Some(states[index].as_ref()?.to_int() << bit)
+            })
+            .sum()
+    }
+
+    /**
+     * Feeds `x` and `y` into the x and y input wires and reads the z wires back.
+     * Bits beyond the circuit's input width are ignored.
+     */
+    fn add(&self, x: u64, y: u64) -> Result<u64, CircuitError> {
+        let inputs = self
+            .wire_names
             .iter()
-            .find(|gate| gate.output_index == wire_index)
-            .unwrap()
+            .filter_map(|(index, name)| {
+                let value = match name.chars().next()? {
+                    'x' => x,
+                    'y' => y,
+                    _ => return None,
+                };
+                let bit = name[1..].parse::<u32>().ok()?;
+                let state = if value.checked_shr(bit).unwrap_or(0) & 1 == 1 {
+                    WireState::On
+                } else {
+                    WireState::Off
+                };
+
+                Some((index, state))
+            })
+            .collect_vec();
+
+        let states = self.evaluate(&inputs)?;
+
+        Ok(self.read_number(&states, 'z'))
     }
 
-    fn verify(&self, wire_name: &str) -> bool {
-        if !wire_name.starts_with("z") {
-            return false;
+    /** Exchanges which wires the gates driving wires `a` and `b` drive */
+    fn swap_outputs(&mut self, a: usize, b: usize) {
+        for gate in self.logic_gates.iter_mut() {
+            if gate.output_index == a {
+                gate.output_index = b;
+            } else if gate.output_index == b {
+                gate.output_index = a;
+            }
         }
+    }
 
-        let bit_number = &wire_name[1..];
-        let parent_gate = self.get_parent_gate(wire_name);
+    /** Number of bits in each of the x and y inputs */
+    fn input_bits(&self) -> usize {
+        self.wire_names
+            .iter()
+            .filter(|(_, name)| {
+                name.strip_prefix('x')
+                    .is_some_and(|bit| bit.bytes().all(|byte| byte.is_ascii_digit()))
+            })
+            .count()
+    }
 
-        if bit_number == "45" && parent_gate.kind == GateType::Or {
-            return true;
+    /** Whether the circuit adds up `samples` pseudo-random pairs of inputs correctly */
+    fn adds_correctly(&self, samples: usize) -> bool {
+        let mask = (1 << self.input_bits()) - 1;
+        let mut rng = XorShift::new(0x2024_1224);
+
+        (0..samples).all(|_| {
+            let (x, y) = (rng.next_u64() & mask, rng.next_u64() & mask);
+
+            self.add(x, y) == Ok(x + y)
+        })
+    }
+
+    /**
+     * Walks the circuit bit by bit checking it against a ripple-carry adder,
+     * swapping gate outputs wherever it breaks that structure:
+     *
+     *   sum   = (x XOR y) XOR carry_in
+     *   carry = (x AND y) OR ((x XOR y) AND carry_in)
+     *
+     * Bit 0 is a half adder, and the last carry is the top z bit. Returns the
+     * swapped wire names, or `None` if the circuit can't be repaired into an
+     * adder without swapping a wire twice.
+     */
+    fn repair_adder(&mut self) -> Option<Vec<String>> {
+        let mut gates = HashMap::new();
+
+        for (gate_index, gate) in self.logic_gates.iter().enumerate() {
+            let mut inputs = gate.inputs_indices;
+            inputs.sort();
+            gates.insert((gate.kind, inputs), gate_index);
         }
 
-        if parent_gate.kind != GateType::Xor {
-            println!("Parent not XOR");
-            return false;
+        let output = |circuit: &Circuit, kind: GateType, a: usize, b: usize| {
+            let inputs = if a < b { [a, b] } else { [b, a] };
+
+            gates
+                .get(&(kind, inputs))
+                .map(|&gate_index| circuit.logic_gates[gate_index].output_index)
+        };
+
+        let input_bits = self.input_bits();
+        let wire = |circuit: &Circuit, prefix: &str, bit: usize| {
+            circuit.get_wire_index(&format!("{prefix}{bit:02}"))
+        };
+        let mut swapped = vec![];
+
+        'restart: loop {
+            let mut carry = None;
+
+            for bit in 0..input_bits {
+                let z = wire(self, "z", bit)?;
+                let (x, y) = (wire(self, "x", bit)?, wire(self, "y", bit)?);
+                let xy_xor = output(self, GateType::Xor, x, y)?;
+                let xy_and = output(self, GateType::And, x, y)?;
+
+                let Some(carry_in) = carry else {
+                    if xy_xor != z {
+                        self.repair_swap(xy_xor, z, &mut swapped)?;
+                        continue 'restart;
+                    }
+
+                    carry = Some(xy_and);
+                    continue;
+                };
+
+                let Some(sum) = output(self, GateType::Xor, xy_xor, carry_in) else {
+                    // The sum gate has one correct input, so its other one is miswired
+                    let (expected, actual) =
+                        self.misplaced_input(GateType::Xor, xy_xor, carry_in)?;
+                    self.repair_swap(expected, actual, &mut swapped)?;
+                    continue 'restart;
+                };
+
+                if sum != z {
+                    self.repair_swap(sum, z, &mut swapped)?;
+                    continue 'restart;
+                }
+
+                let carried = output(self, GateType::And, xy_xor, carry_in)?;
+
+                let Some(carry_out) = output(self, GateType::Or, xy_and, carried) else {
+                    let (expected, actual) = self.misplaced_input(GateType::Or, xy_and, carried)?;
+                    self.repair_swap(expected, actual, &mut swapped)?;
+                    continue 'restart;
+                };
+
+                carry = Some(carry_out);
+            }
+
+            let (carry, top) = (carry?, wire(self, "z", input_bits)?);
+
+            if carry != top {
+                self.repair_swap(carry, top, &mut swapped)?;
+                continue 'restart;
+            }
+
+            break;
         }
 
-        if bit_number == "00" {
-            return true;
+        Some(swapped)
+    }
+
+    /**
+     * Finds the `kind` gate that should combine `a` and `b` but only has one
+     * of them, returning the input it's missing and the wire it has instead
+     */
+    fn misplaced_input(&self, kind: GateType, a: usize, b: usize) -> Option<(usize, usize)> {
+        self.logic_gates
+            .iter()
+            .filter(|gate| gate.kind == kind)
+            .find_map(|gate| match gate.inputs_indices {
+                [input, other] | [other, input] if input == a => Some((b, other)),
+                [input, other] | [other, input] if input == b => Some((a, other)),
+                _ => None,
+            })
+    }
+
+    /** Swaps the outputs of two gates while repairing, giving up on wires that were already swapped */
+    fn repair_swap(&mut self, a: usize, b: usize, swapped: &mut Vec<String>) -> Option<()> {
+        let names = [self.wire_name(a).to_string(), self.wire_name(b).to_string()];
+
+        if names.iter().any(|name| swapped.contains(name)) {
+            return None;
         }
 
-        let input_1 = &self.wires[parent_gate.inputs_indices[0]];
-        let input_2 = &self.wires[parent_gate.inputs_indices[1]];
-
-        let xy_xor = [input_1, input_2].into_iter().find(|wire| {
-            let parent_gate = self.get_parent_gate(&wire.name);
-            parent_gate.kind == GateType::Xor
-        });
-        let carry = [&input_1, &input_2].into_iter().find(|wire| {
-            let parent_gate = self.get_parent_gate(&wire.name);
-            if bit_number == "01" {
-                parent_gate.kind == GateType::And
-            } else {
-                parent_gate.kind == GateType::Or
+        self.swap_outputs(a, b);
+        swapped.extend(names);
+
+        Some(())
+    }
+
+    /**
+     * The circuit as a DOT digraph, with a box for each gate between its
+     * input and output wires. The x, y and z wires are grouped into clusters,
+     * and wires named in `highlighted` stand out.
+     */
+    fn to_dot(&self, highlighted: &[String]) -> Dot {
+        let mut dot = Dot::digraph("circuit");
+
+        for (prefix, label) in [("x", "x inputs"), ("y", "y inputs"), ("z", "z outputs")] {
+            let cluster = dot.add_cluster(label);
+
+            for (_, name) in self.wire_names.iter().sorted_by_key(|&(_, name)| name) {
+                if name
+                    .strip_prefix(prefix)
+                    .is_some_and(|bit| bit.parse::<u32>().is_ok())
+                {
+                    cluster.add_node(name);
+                }
             }
-        });
+        }
 
-        if let Some(xy_xor) = xy_xor {
-            let parent_gate = self.get_parent_gate(&xy_xor.name);
-            let inputs = [
-                &self.wires[parent_gate.inputs_indices[0]].name,
-                &self.wires[parent_gate.inputs_indices[1]].name,
-            ];
+        for (gate_index, gate) in self.logic_gates.iter().enumerate() {
+            let gate_id = format!("gate {gate_index}");
+            let kind = match gate.kind {
+                GateType::And => "AND",
+                GateType::Or => "OR",
+                GateType::Xor => "XOR",
+            };
 
-            if !inputs
-                .iter()
-                .any(|&wire_name| wire_name == &format!("x{bit_number}"))
-                || !inputs
-                    .iter()
-                    .any(|&wire_name| wire_name == &format!("y{bit_number}"))
-            {
-                println!("Wrong XY values");
-                return false;
+            dot.add_node(&gate_id).label(kind).shape("box");
+
+            for input_index in gate.inputs_indices {
+                dot.add_edge(self.wire_name(input_index), &gate_id);
             }
-        } else {
-            println!("No input XOR");
-            return false;
+
+            dot.add_edge(&gate_id, self.wire_name(gate.output_index));
         }
 
-        if carry.is_none() {
-            println!("No carry bit");
-            return false;
+        for name in highlighted {
+            dot.highlight_node(name);
         }
 
-        true
+        dot
     }
 }
 
-fn get_result(circuit: &Circuit, prefix: &str) -> u64 {
-    circuit
-        .wires
-        .iter()
-        .filter(|wire| wire.name.starts_with(prefix))
-        .sorted_by(|lhs, rhs| lhs.name.cmp(&rhs.name))
-        .flat_map(|wire| wire.state.clone().map(|state| state.to_int()))
-        .enumerate()
-        .fold(0, |acc, (i, curr)| acc + (curr << i))
+/** The DOT graph written by `aoc export-graph`, highlighting the wires part 2 swaps */
+pub fn export_graph(input: &str) -> Dot {
+    let swapped = Circuit::parse_input(input)
+        .repair_adder()
+        .unwrap_or_default();
+
+    Circuit::parse_input(input).to_dot(&swapped)
 }
 
 impl Solution for Day24 {
     fn part1(&self, input: &str) -> String {
+        let circuit = Circuit::parse_input(input);
+        let states = circuit
+            .evaluate(&circuit.initial_wire_states)
+            .unwrap_or_else(|error| panic!("Invalid circuit: {error}"));
+
+        circuit.read_number(&states, 'z').to_string()
+    }
+
+    fn part2(&self, input: &str) -> String {
         let mut circuit = Circuit::parse_input(input);
+        let swapped = circuit
+            .repair_adder()
+            .expect("Circuit can't be repaired into an adder");
 
-        circuit.simulate();
-        let result = get_result(&circuit, "z");
+        assert!(
+            circuit.adds_correctly(100),
+            "Repaired circuit doesn't add correctly"
+        );
 
-        result.to_string()
+        swapped.iter().sorted().join(",")
     }
+}
 
-    fn part2(&self, input: &str) -> String {
-        let circuit = Circuit::parse_input(input);
+#[cfg(test)]
+mod tests {
+    use dedent::dedent;
+
+    use super::*;
 
-        for bit in 0..46 {
-            let wire_name = format!("z{bit:02}");
-            print!("Verifying {wire_name}... ");
-            let result = circuit.verify(&wire_name);
-            println!("{}", if result { "PASS!" } else { "FAIL!" });
+    /**
+     * Builds the puzzle input for a `bits`-bit ripple-carry adder, with the
+     * outputs of each pair of wires in `swaps` exchanged. Internal wires are
+     * named after their role and bit, such as `and07`.
+     */
+    fn adder_input(bits: usize, swaps: &[(&str, &str)]) -> String {
+        let mut lines = vec![];
 
-            if !result {
-                circuit.print_wire(&wire_name, 0);
-                println!();
+        for prefix in ["x", "y"] {
+            for bit in 0..bits {
+                lines.push(format!("{prefix}{bit:02}: 0"));
             }
         }
 
-        // Adder value = x XOR y XOR pcarry
-        // Adder carry = (x AND y) OR ((x XOR y) AND pcarry)
-        //
-        // Example:
-        // z <-[XOR]
-        //     |...-[XOR]
-        //     |    |...-x
-        //     |    |...-y
-        //     |...-[OR]
-        //          |...-[AND]
-        //          |    |...-x'
-        //          |    |...-y'
-        //          |...-[AND]
-        //               |...-pcarry (x'' y'')
-        //               |...-[XOR]
-        //                    |...-x'
-        //                    |...-y'
-
-        // Found by inspecting output of above and comparing with wiring diagram
-        let swapped_gates = ["krj", "bpt", "ngr", "z11", "fkp", "z06", "mfm", "z31"];
-
-        swapped_gates.iter().sorted().join(",")
+        lines.push(String::new());
+
+        let mut gate = |a: String, kind: &str, b: String, output: String| {
+            let output = swaps
+                .iter()
+                .find_map(|&(from, to)| match output.as_str() {
+                    name if name == from => Some(to.to_string()),
+                    name if name == to => Some(from.to_string()),
+                    _ => None,
+                })
+                .unwrap_or(output);
+
+            lines.push(format!("{a} {kind} {b} -> {output}"));
+        };
+
+        // The final carry is the top output bit
+        let carry = |bit: usize| {
+            if bit == bits - 1 {
+                format!("z{bits:02}")
+            } else {
+                format!("car{bit:02}")
+            }
+        };
+
+        gate("x00".into(), "XOR", "y00".into(), "z00".into());
+        gate("x00".into(), "AND", "y00".into(), carry(0));
+
+        for bit in 1..bits {
+            let (x, y) = (format!("x{bit:02}"), format!("y{bit:02}"));
+            let (carry_in, carry_out) = (carry(bit - 1), carry(bit));
+
+            gate(x.clone(), "XOR", y.clone(), format!("xor{bit:02}"));
+            gate(x, "AND", y, format!("and{bit:02}"));
+            gate(
+                format!("xor{bit:02}"),
+                "XOR",
+                carry_in.clone(),
+                format!("z{bit:02}"),
+            );
+            gate(
+                format!("xor{bit:02}"),
+                "AND",
+                carry_in,
+                format!("cin{bit:02}"),
+            );
+            gate(
+                format!("and{bit:02}"),
+                "OR",
+                format!("cin{bit:02}"),
+                carry_out,
+            );
+        }
+
+        lines.join("\n")
+    }
+
+    #[test]
+    fn test_day24() {
+        let input = dedent!(
+            "
+            x00: 1
+            x01: 1
+            x02: 1
+            y00: 0
+            y01: 1
+            y02: 0
+
+            x00 AND y00 -> z00
+            x01 XOR y01 -> z01
+            x02 OR y02 -> z02
+            "
+        );
+
+        assert_eq!(Day24.part1(input), "4");
+    }
+
+    #[test]
+    fn test_day24_evaluation_errors() {
+        let mut circuit = Circuit::parse_input(&adder_input(8, &[]));
+        let [xor, z] = ["xor05", "z05"].map(|name| circuit.get_wire_index(name).unwrap());
+
+        // z05 = xor05 XOR car04, so driving xor05 from that gate loops it onto itself
+        circuit.swap_outputs(xor, z);
+        assert_eq!(
+            circuit.add(1, 2),
+            Err(CircuitError::Cycle(vec!["xor05".to_string()]))
+        );
+
+        circuit.swap_outputs(xor, z);
+        assert_eq!(circuit.add(1, 2), Ok(3));
+
+        let circuit = Circuit::parse_input("x00: 1\ny00: 1\n\nx00 AND foo -> z00");
+        assert_eq!(
+            circuit.add(1, 1),
+            Err(CircuitError::UndrivenWire("foo".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_day24_adds_like_integers() {
+        let mut rng = XorShift::new(0x5eed);
+
+        for bits in [1, 2, 7, 16, 45] {
+            let circuit = Circuit::parse_input(&adder_input(bits, &[]));
+            let mask = (1 << bits) - 1;
+            let edge_cases = [(0, 0), (mask, 0), (0, mask), (mask, mask), (1, mask)];
+            let random_cases = (0..200).map(|_| (rng.next_u64() & mask, rng.next_u64() & mask));
+
+            for (x, y) in edge_cases.into_iter().chain(random_cases) {
+                assert_eq!(circuit.add(x, y), Ok(x + y), "{bits} bits: {x} + {y}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_day24_intact_adder() {
+        let mut circuit = Circuit::parse_input(&adder_input(45, &[]));
+
+        assert_eq!(circuit.repair_adder(), Some(vec![]));
+        assert!(circuit.adds_correctly(50));
+    }
+
+    #[test]
+    fn test_day24_repair() {
+        let swaps = [
+            ("xor05", "and05"),
+            ("z12", "car12"),
+            ("z20", "cin20"),
+            ("z31", "and31"),
+        ];
+        let input = adder_input(45, &swaps);
+
+        let mut circuit = Circuit::parse_input(&input);
+        assert!(!circuit.adds_correctly(50));
+
+        let mut swapped = circuit.repair_adder().unwrap();
+        swapped.sort();
+
+        let mut expected = swaps.iter().flat_map(|&(a, b)| [a, b]).collect_vec();
+        expected.sort();
+
+        assert_eq!(swapped, expected);
+        assert!(circuit.adds_correctly(200));
+        assert_eq!(Day24.part2(&input), expected.join(","));
+
+        let dot = export_graph(&input).to_string();
+        assert_eq!(dot.matches("fillcolor").count(), 8);
+        assert!(dot.contains("\"gate 0\" -> \"z00\";"));
     }
 }
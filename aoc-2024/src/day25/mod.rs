@@ -1,4 +1,4 @@
-use shared::Solution;
+use shared::{Grid, Solution};
 
 pub struct Day25;
 
@@ -17,46 +17,26 @@ struct Schematics {
 
 impl Schematics {
     fn parse_input(input: &str) -> Schematics {
-        let mut lines = input.trim().lines();
-
         let mut locks = vec![];
         let mut keys = vec![];
 
-        while let Some(line) = lines.next() {
-            let is_lock = line == "#####";
-
-            if is_lock {
-                let mut pin_heights = [0; 5];
-
-                for _ in 0..5 {
-                    let line = lines.next().unwrap();
+        for schematic in input.trim().split("\n\n") {
+            let grid = Grid::<u8>::parse(schematic).unwrap();
+            let is_lock = grid.rows().next().unwrap().iter().all(|&cell| cell == b'#');
 
-                    for (col, height) in pin_heights.iter_mut().enumerate() {
-                        if let Some('#') = line.chars().nth(col) {
-                            *height += 1;
-                        }
-                    }
-                }
+            // Each column becomes a row, minus the full top or bottom row
+            let mut heights = [0; 5];
+            for (height, column) in heights.iter_mut().zip(grid.transpose().rows()) {
+                *height = column.iter().filter(|&&cell| cell == b'#').count() as u8 - 1;
+            }
 
-                locks.push(Lock { pin_heights });
+            if is_lock {
+                locks.push(Lock {
+                    pin_heights: heights,
+                });
             } else {
-                let mut heights = [0; 5];
-
-                for _ in 0..5 {
-                    let line = lines.next().unwrap();
-
-                    for (col, height) in heights.iter_mut().enumerate() {
-                        if let Some('#') = line.chars().nth(col) {
-                            *height += 1;
-                        }
-                    }
-                }
-
                 keys.push(Key { heights });
             }
-
-            lines.next(); // Skip bottom
-            lines.next(); // Skip whitespace
         }
 
         Schematics { locks, keys }
@@ -1,5 +1,5 @@
 // Advent of Code 2024
-use shared::Solution;
+use shared::{Dot, Solution};
 use std::fs;
 
 pub mod day01;
@@ -63,6 +63,29 @@ pub fn run_day(day: u32, input_path: &str) {
     }
 }
 
+/// Runs the day's program on its VM, printing the disassembly and an execution trace
+pub fn trace_day(day: u32, input_path: &str) {
+    let input = fs::read_to_string(input_path)
+        .unwrap_or_else(|_| panic!("Failed to read input file: {input_path}"));
+
+    match day {
+        17 => day17::trace(&input),
+        _ => eprintln!("Day {day} has no VM program to trace for 2024"),
+    }
+}
+
+/// Builds the day's puzzle structure as a DOT graph, if it has one to draw
+pub fn export_graph(day: u32, input_path: &str) -> Option<Dot> {
+    let input = fs::read_to_string(input_path)
+        .unwrap_or_else(|_| panic!("Failed to read input file: {input_path}"));
+
+    match day {
+        23 => Some(day23::export_graph(&input)),
+        24 => Some(day24::export_graph(&input)),
+        _ => None,
+    }
+}
+
 fn run_solution<T: Solution>(solution: &T, input: &str) {
     use std::time::Instant;
 
@@ -1,8 +1,8 @@
-use shared::{Coordinate, Solution};
+use shared::{BitGrid, FromByte, Grid, Solution};
 
 pub struct Day4;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, PartialEq)]
 enum Cell {
     Empty,
     Paper,
@@ -10,16 +10,14 @@ enum Cell {
 
 #[derive(Debug)]
 struct Map {
-    row_count: usize,
-    col_count: usize,
-    cells: Vec<Cell>,
+    paper: BitGrid,
 }
 
 impl Solution for Day4 {
     fn part1(&self, input: &str) -> String {
         let map = Day4::parse_input(input);
 
-        map.find_removable_paper().len().to_string()
+        map.find_removable_paper().count_ones().to_string()
     }
 
     fn part2(&self, input: &str) -> String {
@@ -28,13 +26,8 @@ impl Solution for Day4 {
         let mut removable_paper = map.find_removable_paper();
 
         while !removable_paper.is_empty() {
-            total_removed += removable_paper.len();
-
-            for coord in removable_paper {
-                if let Some(index) = map.get_index(&coord) {
-                    map.cells[index] = Cell::Empty;
-                }
-            }
+            total_removed += removable_paper.count_ones();
+            map.paper -= &removable_paper;
 
             removable_paper = map.find_removable_paper();
         }
@@ -44,96 +37,55 @@ impl Solution for Day4 {
 }
 
 impl Map {
-    fn get_index(&self, coord: &Coordinate) -> Option<usize> {
-        if coord.row() >= self.row_count || coord.col() >= self.col_count {
-            return None;
-        }
-
-        Some(coord.row() * self.col_count + coord.col())
-    }
-
-    fn get_at_coord(&self, coord: &Coordinate) -> Option<Cell> {
-        if let Some(index) = self.get_index(coord) {
-            self.cells.get(index).cloned()
-        } else {
-            None
-        }
-    }
-
-    fn get_neighbors(&self, coord: &Coordinate) -> Vec<Cell> {
-        let mut result = vec![];
-
-        for row in coord.row().saturating_sub(1)..=coord.row() + 1 {
-            for col in coord.col().saturating_sub(1)..=coord.col() + 1 {
-                if row == coord.row() && col == coord.col() {
-                    continue;
-                }
-
-                if let Some(cell) = self.get_at_coord(&Coordinate(row, col)) {
-                    result.push(cell);
-                }
-            }
-        }
+    /** Paper rolls with fewer than four rolls among their eight neighbours */
+    fn find_removable_paper(&self) -> BitGrid {
+        let mut result = self.paper.clone();
+        result -= &self.paper.neighbour_counts().at_least(4);
 
         result
     }
+}
 
-    fn find_removable_paper(&self) -> Vec<Coordinate> {
-        let mut result = vec![];
-
-        for row in 0..self.row_count {
-            for col in 0..self.col_count {
-                let coord = Coordinate(row, col);
-
-                if let Some(Cell::Paper) = self.get_at_coord(&coord) {
-                    let neighbors = self.get_neighbors(&coord);
-                    let paper_neighbor_count = neighbors
-                        .iter()
-                        .filter(|&neighbor| match neighbor {
-                            Cell::Empty => false,
-                            Cell::Paper => true,
-                        })
-                        .count();
-
-                    if paper_neighbor_count < 4 {
-                        result.push(coord);
-                    }
-                }
-            }
+impl FromByte for Cell {
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            b'.' => Some(Cell::Empty),
+            b'@' => Some(Cell::Paper),
+            _ => None,
         }
-
-        result
     }
 }
 
 impl Day4 {
     fn parse_input(input: &str) -> Map {
-        let mut row_count = 0;
-        let mut col_count = 0;
-        let mut cells = vec![];
-
-        for line in input.lines().filter(|line| !line.is_empty()) {
-            row_count += 1;
-
-            for char in line.chars() {
-                let cell = match char {
-                    '.' => Cell::Empty,
-                    '@' => Cell::Paper,
-                    _ => panic!("Invalid input"),
-                };
-
-                cells.push(cell);
-            }
-
-            if col_count == 0 {
-                col_count = cells.len();
-            }
-        }
+        let grid = Grid::<Cell>::parse(input).expect("Invalid input");
 
         Map {
-            row_count,
-            col_count,
-            cells,
+            paper: BitGrid::from_grid(&grid, |cell| *cell == Cell::Paper),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+..@@.@@@@.
+@@@.@.@.@@
+@@@@@.@.@@
+@.@@@@..@.
+@@.@@@@.@@
+.@@@@@@@.@
+.@.@.@.@@@
+@.@@@.@@@@
+.@@@@@@@@.
+@.@.@@@.@.
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(Day4.part1(EXAMPLE), "13");
+        assert_eq!(Day4.part2(EXAMPLE), "43");
+    }
+}
@@ -1,25 +1,19 @@
-use std::{collections::HashSet, str::FromStr};
+use std::collections::HashSet;
 
-use anyhow::anyhow;
-use shared::{Coordinate, Grid, GridCursor, Solution};
+use shared::{FromByte, Grid, GridCursor, Solution};
 
 pub struct Day7;
 
 #[derive(Debug)]
 enum Cell {
     Empty,
-    Start,
     Splitter,
 }
 
 impl Solution for Day7 {
     fn part1(&self, input: &str) -> String {
-        let problem = Grid::<Cell>::new(input);
-        let mut cursor = problem.get_cursor(&Coordinate::new(0, 0));
-
-        while !matches!(cursor.value(), Cell::Start) {
-            cursor.next();
-        }
+        let (problem, markers) = Grid::<Cell>::parse_with_markers(input, &[(b'S', b'.')]).unwrap();
+        let cursor = problem.get_cursor(markers.get(b'S').unwrap());
 
         let mut beam_cursors = vec![cursor];
         let mut splitter_positions = HashSet::new();
@@ -67,20 +61,17 @@ impl Solution for Day7 {
         splitter_positions.len().to_string()
     }
 
-    fn part2(&self, input: &str) -> String {
+    fn part2(&self, _input: &str) -> String {
         String::from("todo")
     }
 }
 
-impl FromStr for Cell {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "." => Ok(Cell::Empty),
-            "S" => Ok(Cell::Start),
-            "^" => Ok(Cell::Splitter),
-            _ => Err(anyhow!("Invalid string")),
+impl FromByte for Cell {
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            b'.' => Some(Cell::Empty),
+            b'^' => Some(Cell::Splitter),
+            _ => None,
         }
     }
 }
@@ -9,7 +9,18 @@ use std::time::Instant;
 use syn::{parse_file, File, Item, ItemMod, Visibility};
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
+    let mut args: Vec<String> = env::args().collect();
+
+    // Global flags can go anywhere; strip them before reading positional arguments
+    args.retain(|arg| {
+        let is_verbose_flag = arg == "--verbose" || arg == "-v";
+
+        if is_verbose_flag {
+            shared::set_verbose(true);
+        }
+
+        !is_verbose_flag
+    });
 
     if args.len() < 3 {
         print_usage();
@@ -30,6 +41,39 @@ fn main() {
 
             run_solution(year, day);
         }
+        "trace" => {
+            if args.len() != 4 {
+                eprintln!("Usage: aoc trace <year> <day>");
+                process::exit(1);
+            }
+
+            let year: u32 = parse_year(&args[2]);
+            let day: u32 = parse_day(&args[3]);
+
+            trace_solution(year, day);
+        }
+        "export-graph" => {
+            if args.len() != 4 {
+                eprintln!("Usage: aoc export-graph <year> <day>");
+                process::exit(1);
+            }
+
+            let year: u32 = parse_year(&args[2]);
+            let day: u32 = parse_day(&args[3]);
+
+            export_graph(year, day);
+        }
+        "report" => {
+            if !(4..=5).contains(&args.len()) {
+                eprintln!("Usage: aoc report <year> <day> [path]");
+                process::exit(1);
+            }
+
+            let year: u32 = parse_year(&args[2]);
+            let day: u32 = parse_day(&args[3]);
+
+            report_solution(year, day, args.get(4).map(String::as_str));
+        }
         "create" => {
             if args.len() != 4 {
                 eprintln!("Usage: aoc create <year> <day>");
@@ -53,11 +97,25 @@ fn print_usage() {
     println!("Advent of Code Runner");
     println!();
     println!("Usage:");
-    println!("  aoc run <year> <day>     - Run solution for a specific year and day");
-    println!("  aoc create <year> <day>  - Create solution template for a specific year and day");
+    println!("  aoc run <year> <day>           - Run solution for a specific year and day");
+    println!("  aoc trace <year> <day>         - Disassemble and trace the day's VM program");
+    println!("  aoc export-graph <year> <day>  - Write the day's puzzle structure as a Graphviz .dot file");
+    println!("  aoc report <year> <day> [path] - Print a report on the day's puzzle structure, from path if given");
+    println!(
+        "  aoc create <year> <day>        - Create solution template for a specific year and day"
+    );
+    println!();
+    println!("Options:");
+    println!(
+        "  -v, --verbose                  - Print diagnostics such as memoization cache statistics"
+    );
     println!();
     println!("Examples:");
     println!("  aoc run 2024 1");
+    println!("  aoc run 2024 11 --verbose");
+    println!("  aoc trace 2024 17");
+    println!("  aoc export-graph 2024 24");
+    println!("  aoc report 2022 7 /a");
     println!("  aoc create 2024 2");
 }
 
@@ -115,6 +173,72 @@ fn run_solution(year: u32, day: u32) {
     }
 }
 
+fn trace_solution(year: u32, day: u32) {
+    let input_path = format!("aoc-{year}/src/day{day:02}/input.txt");
+
+    println!(
+        "{}",
+        format!("=== Advent of Code {year} - Day {day} (trace) ===")
+            .bright_cyan()
+            .bold()
+    );
+    println!();
+
+    match year {
+        2022 => aoc_2022::trace_day(day, &input_path),
+        2024 => aoc_2024::trace_day(day, &input_path),
+        _ => {
+            eprintln!("Year {year} has no VM programs to trace");
+            process::exit(1);
+        }
+    }
+}
+
+fn report_solution(year: u32, day: u32, path: Option<&str>) {
+    let input_path = format!("aoc-{year}/src/day{day:02}/input.txt");
+
+    println!(
+        "{}",
+        format!("=== Advent of Code {year} - Day {day} (report) ===")
+            .bright_cyan()
+            .bold()
+    );
+    println!();
+
+    match year {
+        2022 => aoc_2022::report_day(day, &input_path, path),
+        _ => {
+            eprintln!("Year {year} has no reports");
+            process::exit(1);
+        }
+    }
+}
+
+fn export_graph(year: u32, day: u32) {
+    let input_path = format!("aoc-{year}/src/day{day:02}/input.txt");
+
+    let dot = match year {
+        2022 => aoc_2022::export_graph(day, &input_path),
+        2024 => aoc_2024::export_graph(day, &input_path),
+        _ => None,
+    };
+
+    let Some(dot) = dot else {
+        eprintln!("Day {day} of {year} has no graph to export");
+        process::exit(1);
+    };
+
+    let output_path = format!("aoc-{year}-day{day:02}.dot");
+
+    if let Err(e) = fs::write(&output_path, dot.to_string()) {
+        eprintln!("Error writing {output_path}: {e}");
+        process::exit(1);
+    }
+
+    println!("{}", format!("✓ Wrote {output_path}").green());
+    println!("Render it with: dot -Tsvg {output_path} -o aoc-{year}-day{day:02}.svg");
+}
+
 /// Runs the solution for a specific day
 pub fn run_day(solution: Box<dyn Solution>, input_path: &str) {
     let input = fs::read_to_string(input_path)
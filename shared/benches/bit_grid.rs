@@ -0,0 +1,112 @@
+use std::{hint::black_box, time::Instant};
+
+use shared::{BitGrid, Coordinate, Grid, GridView, XorShift};
+
+const SIZE: usize = 140;
+const ITERATIONS: u32 = 200;
+
+/// Builds a deterministic SIZE x SIZE grid, about 62% paper like a 2025 day 4 input
+fn generate_paper() -> Grid<bool> {
+    let mut rng = XorShift::new(0x2545f491);
+
+    Grid::from_fn(SIZE, SIZE, |_| rng.below(8) < 5)
+}
+
+/// 2025 day 4's `find_removable_paper`: paper with fewer than four paper neighbours
+fn find_removable_paper(paper: &BitGrid) -> BitGrid {
+    let mut result = paper.clone();
+    result -= &paper.neighbour_counts().at_least(4);
+
+    result
+}
+
+/// Part 2: keeps removing paper until none is removable
+fn remove_all(mut paper: BitGrid) -> usize {
+    let mut total_removed = 0;
+    let mut removable = find_removable_paper(&paper);
+
+    while !removable.is_empty() {
+        total_removed += removable.count_ones();
+        paper -= &removable;
+        removable = find_removable_paper(&paper);
+    }
+
+    total_removed
+}
+
+/// The same search cell by cell, as day 4 did before moving to `BitGrid`
+fn find_removable_paper_per_cell(paper: &Grid<bool>) -> Vec<Coordinate> {
+    let mut result = vec![];
+
+    for row in 0..paper.row_count() {
+        for col in 0..paper.col_count() {
+            let position = Coordinate::new(row, col);
+            let neighbours = paper
+                .all_neighbours(&position)
+                .iter()
+                .filter(|neighbour| *paper.get(neighbour))
+                .count();
+
+            if *paper.get(&position) && neighbours < 4 {
+                result.push(position);
+            }
+        }
+    }
+
+    result
+}
+
+fn remove_all_per_cell(mut paper: Grid<bool>) -> usize {
+    let mut total_removed = 0;
+    let mut removable = find_removable_paper_per_cell(&paper);
+
+    while !removable.is_empty() {
+        total_removed += removable.len();
+
+        for position in &removable {
+            *paper.get_mut(position) = false;
+        }
+
+        removable = find_removable_paper_per_cell(&paper);
+    }
+
+    total_removed
+}
+
+fn bench<T>(name: &str, mut f: impl FnMut() -> T) {
+    // Warm up
+    for _ in 0..10 {
+        black_box(f());
+    }
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(f());
+    }
+    let duration = start.elapsed() / ITERATIONS;
+
+    println!("{name:<28} {duration:?} / iteration");
+}
+
+fn main() {
+    let grid = generate_paper();
+    let paper = BitGrid::from_grid(&grid, |&cell| cell);
+
+    assert_eq!(
+        find_removable_paper(&paper).count_ones(),
+        find_removable_paper_per_cell(&grid).len()
+    );
+    assert_eq!(remove_all(paper.clone()), remove_all_per_cell(grid.clone()));
+
+    println!("Removing paper from a {SIZE}x{SIZE} grid");
+    bench("find_removable_paper", || {
+        find_removable_paper(black_box(&paper))
+    });
+    bench("find_removable_paper (cells)", || {
+        find_removable_paper_per_cell(black_box(&grid))
+    });
+    bench("remove all", || remove_all(black_box(paper.clone())));
+    bench("remove all (cells)", || {
+        remove_all_per_cell(black_box(grid.clone()))
+    });
+}
@@ -0,0 +1,75 @@
+use std::{hint::black_box, str::FromStr, time::Instant};
+
+use shared::{FromByte, Grid, XorShift};
+
+const SIZE: usize = 140;
+const ITERATIONS: u32 = 500;
+
+#[derive(Debug)]
+enum Tile {
+    Open,
+    Wall,
+}
+
+impl FromStr for Tile {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "." => Ok(Tile::Open),
+            "#" => Ok(Tile::Wall),
+            _ => Err(format!("Invalid tile {s}")),
+        }
+    }
+}
+
+impl FromByte for Tile {
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            b'.' => Some(Tile::Open),
+            b'#' => Some(Tile::Wall),
+            _ => None,
+        }
+    }
+}
+
+/// Builds a deterministic SIZE x SIZE maze-like input
+fn generate_input() -> String {
+    let mut rng = XorShift::new(0x2545f491);
+    let mut input = String::with_capacity(SIZE * (SIZE + 1));
+
+    for _ in 0..SIZE {
+        for _ in 0..SIZE {
+            input.push(if rng.below(4) == 0 { '#' } else { '.' });
+        }
+
+        input.push('\n');
+    }
+
+    input
+}
+
+fn bench<T>(name: &str, mut f: impl FnMut() -> T) {
+    // Warm up
+    for _ in 0..10 {
+        black_box(f());
+    }
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(f());
+    }
+    let duration = start.elapsed() / ITERATIONS;
+
+    println!("{name:<12} {duration:?} / iteration");
+}
+
+fn main() {
+    let input = generate_input();
+
+    println!("Parsing a {SIZE}x{SIZE} grid");
+    bench("Grid::new", || Grid::<Tile>::new(black_box(&input)));
+    bench("Grid::parse", || {
+        Grid::<Tile>::parse(black_box(&input)).unwrap()
+    });
+}
@@ -0,0 +1,393 @@
+use std::ops::{BitAndAssign, BitOrAssign, BitXorAssign, SubAssign};
+
+use crate::{Coordinate, Grid};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A dense grid of booleans packed 64 cells to a word, so whole rows can be
+/// combined, shifted and counted a word at a time.
+///
+/// Each row starts on a fresh word and the unused bits at the end of a row
+/// are always clear.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitGrid {
+    row_count: usize,
+    col_count: usize,
+    words_per_row: usize,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    /// An all-clear grid
+    pub fn new(row_count: usize, col_count: usize) -> BitGrid {
+        let words_per_row = col_count.div_ceil(WORD_BITS);
+
+        BitGrid {
+            row_count,
+            col_count,
+            words_per_row,
+            words: vec![0; row_count * words_per_row],
+        }
+    }
+
+    /// Builds a grid by calling `is_set` for every position in row-major order
+    pub fn from_fn(
+        row_count: usize,
+        col_count: usize,
+        mut is_set: impl FnMut(Coordinate) -> bool,
+    ) -> BitGrid {
+        let mut grid = BitGrid::new(row_count, col_count);
+
+        for row in 0..row_count {
+            for col in 0..col_count {
+                let position = Coordinate::new(row, col);
+
+                if is_set(position.clone()) {
+                    grid.set(&position, true);
+                }
+            }
+        }
+
+        grid
+    }
+
+    /// Sets the cells of `grid` that match `predicate`
+    pub fn from_grid<TCell>(grid: &Grid<TCell>, predicate: impl Fn(&TCell) -> bool) -> BitGrid {
+        BitGrid::from_fn(grid.row_count(), grid.col_count(), |position| {
+            predicate(grid.get(&position))
+        })
+    }
+
+    pub fn row_count(&self) -> usize {
+        self.row_count
+    }
+
+    pub fn col_count(&self) -> usize {
+        self.col_count
+    }
+
+    pub fn get(&self, position: &Coordinate) -> bool {
+        let (index, bit) = self.locate(position);
+
+        self.words[index] >> bit & 1 == 1
+    }
+
+    pub fn set(&mut self, position: &Coordinate, value: bool) {
+        let (index, bit) = self.locate(position);
+
+        if value {
+            self.words[index] |= 1 << bit;
+        } else {
+            self.words[index] &= !(1 << bit);
+        }
+    }
+
+    /// The packed words of `row`, with column `c` at bit `c % 64` of word `c / 64`
+    pub fn row_words(&self, row: usize) -> &[u64] {
+        let start = row * self.words_per_row;
+
+        &self.words[start..start + self.words_per_row]
+    }
+
+    /// Number of set cells
+    pub fn count_ones(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    /// Positions of the set cells in row-major order
+    pub fn iter_ones(&self) -> impl Iterator<Item = Coordinate> + '_ {
+        self.words
+            .iter()
+            .enumerate()
+            .flat_map(move |(index, &word)| {
+                let row = index / self.words_per_row;
+                let first_col = (index % self.words_per_row) * WORD_BITS;
+
+                SetBits(word).map(move |bit| Coordinate::new(row, first_col + bit))
+            })
+    }
+
+    /// Counts the set cells among each cell's eight neighbours
+    pub fn neighbour_counts(&self) -> NeighbourCounts {
+        let mut planes = [(); 4].map(|_| BitGrid::new(self.row_count, self.col_count));
+        let empty_row = vec![0; self.words_per_row];
+
+        for row in 0..self.row_count {
+            let above = match row {
+                0 => &empty_row[..],
+                _ => self.row_words(row - 1),
+            };
+            let current = self.row_words(row);
+            let below = match row + 1 < self.row_count {
+                true => self.row_words(row + 1),
+                false => &empty_row[..],
+            };
+
+            for word in 0..self.words_per_row {
+                let mut sum = [0; 4];
+
+                for neighbours in [above, current, below] {
+                    add_sliced(&mut sum, shifted_left(neighbours, word));
+                    add_sliced(&mut sum, shifted_right(neighbours, word));
+                }
+
+                add_sliced(&mut sum, above[word]);
+                add_sliced(&mut sum, below[word]);
+
+                let index = row * self.words_per_row + word;
+                let padding = self.padding_mask(word);
+
+                for (plane, bits) in planes.iter_mut().zip(sum) {
+                    plane.words[index] = bits & padding;
+                }
+            }
+        }
+
+        NeighbourCounts { planes }
+    }
+
+    fn locate(&self, position: &Coordinate) -> (usize, usize) {
+        if position.row() >= self.row_count || position.col() >= self.col_count {
+            panic!("Index out of bounds");
+        }
+
+        (
+            position.row() * self.words_per_row + position.col() / WORD_BITS,
+            position.col() % WORD_BITS,
+        )
+    }
+
+    /// The bits of word `word` in each row that belong to real columns
+    fn padding_mask(&self, word: usize) -> u64 {
+        let used = self.col_count - word * WORD_BITS;
+
+        if used >= WORD_BITS {
+            !0
+        } else {
+            (1 << used) - 1
+        }
+    }
+
+    fn assert_same_shape(&self, other: &BitGrid) {
+        assert_eq!(
+            (self.row_count, self.col_count),
+            (other.row_count, other.col_count),
+            "bit grids have different shapes"
+        );
+    }
+}
+
+/// Word `word` of `row` moved one column right, so each bit holds its left neighbour
+fn shifted_left(row: &[u64], word: usize) -> u64 {
+    let carry = match word {
+        0 => 0,
+        _ => row[word - 1] >> (WORD_BITS - 1),
+    };
+
+    row[word] << 1 | carry
+}
+
+/// Word `word` of `row` moved one column left, so each bit holds its right neighbour
+fn shifted_right(row: &[u64], word: usize) -> u64 {
+    let carry = row.get(word + 1).map_or(0, |next| next << (WORD_BITS - 1));
+
+    row[word] >> 1 | carry
+}
+
+/// Adds a one-bit value into each lane of a bit-sliced counter
+fn add_sliced(sum: &mut [u64; 4], mut carry: u64) {
+    for plane in sum.iter_mut() {
+        let next_carry = *plane & carry;
+        *plane ^= carry;
+        carry = next_carry;
+    }
+}
+
+struct SetBits(u64);
+
+impl Iterator for SetBits {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.0 == 0 {
+            return None;
+        }
+
+        let bit = self.0.trailing_zeros() as usize;
+        self.0 &= self.0 - 1;
+
+        Some(bit)
+    }
+}
+
+/// Per-cell neighbour counts from [`BitGrid::neighbour_counts`], stored as
+/// four bit planes of a 0–8 binary count.
+#[derive(Debug, Clone)]
+pub struct NeighbourCounts {
+    planes: [BitGrid; 4],
+}
+
+impl NeighbourCounts {
+    pub fn get(&self, position: &Coordinate) -> u8 {
+        self.planes
+            .iter()
+            .enumerate()
+            .map(|(bit, plane)| (plane.get(position) as u8) << bit)
+            .sum()
+    }
+
+    /// The cells with at least `count` set neighbours
+    pub fn at_least(&self, count: u8) -> BitGrid {
+        let mut result = self.planes[0].clone();
+
+        for (index, word) in result.words.iter_mut().enumerate() {
+            // Compare against `count` from the most significant plane down
+            let mut greater = 0;
+            let mut equal = !0;
+
+            for (bit, plane) in self.planes.iter().enumerate().rev() {
+                let plane = plane.words[index];
+
+                if count >> bit & 1 == 1 {
+                    equal &= plane;
+                } else {
+                    greater |= equal & plane;
+                    equal &= !plane;
+                }
+            }
+
+            let padding = self.planes[0].padding_mask(index % self.planes[0].words_per_row);
+            *word = match count {
+                0..=15 => (greater | equal) & padding,
+                _ => 0,
+            };
+        }
+
+        result
+    }
+}
+
+impl BitAndAssign<&BitGrid> for BitGrid {
+    fn bitand_assign(&mut self, other: &BitGrid) {
+        self.assert_same_shape(other);
+        self.words
+            .iter_mut()
+            .zip(&other.words)
+            .for_each(|(word, other)| *word &= other);
+    }
+}
+
+impl BitOrAssign<&BitGrid> for BitGrid {
+    fn bitor_assign(&mut self, other: &BitGrid) {
+        self.assert_same_shape(other);
+        self.words
+            .iter_mut()
+            .zip(&other.words)
+            .for_each(|(word, other)| *word |= other);
+    }
+}
+
+impl BitXorAssign<&BitGrid> for BitGrid {
+    fn bitxor_assign(&mut self, other: &BitGrid) {
+        self.assert_same_shape(other);
+        self.words
+            .iter_mut()
+            .zip(&other.words)
+            .for_each(|(word, other)| *word ^= other);
+    }
+}
+
+/// Clears every cell that is set in `other`
+impl SubAssign<&BitGrid> for BitGrid {
+    fn sub_assign(&mut self, other: &BitGrid) {
+        self.assert_same_shape(other);
+        self.words
+            .iter_mut()
+            .zip(&other.words)
+            .for_each(|(word, other)| *word &= !other);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A pseudo-random grid wide enough to span several words per row
+    fn scattered(row_count: usize, col_count: usize) -> BitGrid {
+        BitGrid::from_fn(row_count, col_count, |position| {
+            (position.row() * 31 + position.col() * 17) % 7 < 3
+        })
+    }
+
+    #[test]
+    fn get_set_and_count() {
+        let mut grid = BitGrid::new(3, 130);
+        grid.set(&Coordinate::new(1, 64), true);
+        grid.set(&Coordinate::new(2, 129), true);
+        grid.set(&Coordinate::new(0, 0), true);
+        grid.set(&Coordinate::new(0, 0), false);
+
+        assert!(grid.get(&Coordinate::new(1, 64)));
+        assert!(!grid.get(&Coordinate::new(1, 63)));
+        assert_eq!(grid.count_ones(), 2);
+        assert_eq!(
+            grid.iter_ones().collect::<Vec<_>>(),
+            vec![Coordinate::new(1, 64), Coordinate::new(2, 129)]
+        );
+    }
+
+    #[test]
+    fn neighbour_counts_match_naive() {
+        let grid = scattered(9, 150);
+        let counts = grid.neighbour_counts();
+
+        for row in 0..9usize {
+            for col in 0..150usize {
+                let expected = (row.saturating_sub(1)..=(row + 1).min(8))
+                    .flat_map(|r| (col.saturating_sub(1)..=(col + 1).min(149)).map(move |c| (r, c)))
+                    .filter(|&(r, c)| (r, c) != (row, col))
+                    .filter(|&(r, c)| grid.get(&Coordinate::new(r, c)))
+                    .count();
+
+                assert_eq!(counts.get(&Coordinate::new(row, col)) as usize, expected);
+
+                for threshold in 0..=9 {
+                    assert_eq!(
+                        counts.at_least(threshold).get(&Coordinate::new(row, col)),
+                        expected >= threshold as usize
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn word_operations() {
+        let a = scattered(4, 70);
+        let b = BitGrid::from_fn(4, 70, |position| position.col() % 2 == 0);
+
+        let mut difference = a.clone();
+        difference -= &b;
+        let mut intersection = a.clone();
+        intersection &= &b;
+
+        assert_eq!(
+            difference.count_ones() + intersection.count_ones(),
+            a.count_ones()
+        );
+
+        let mut union = difference.clone();
+        union |= &intersection;
+        assert_eq!(union, a);
+
+        union ^= &a;
+        assert!(union.is_empty());
+    }
+}
@@ -0,0 +1,156 @@
+use std::{collections::HashMap, hash::Hash};
+
+/// A repeating run of states in a deterministic simulation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<T> {
+    /// Number of steps before the cycle is first entered
+    pub start: usize,
+    /// Steps it takes to return to the same state
+    pub length: usize,
+    /// The state at step `start`, the first on the cycle
+    pub state: T,
+}
+
+/// Finds the cycle that `step` must eventually enter from `initial`, using
+/// Brent's algorithm so only a couple of states are held at once.
+///
+/// Never returns if the states don't repeat.
+pub fn find_cycle<T: Clone + Eq>(initial: T, mut step: impl FnMut(&T) -> T) -> Cycle<T> {
+    // Find the length by teleporting the tortoise to the hare at each power of two
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+
+        hare = step(&hare);
+        length += 1;
+    }
+
+    // With the hare `length` steps ahead, they meet at the start of the cycle
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+
+    let mut start = 0;
+
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle {
+        start,
+        length,
+        state: tortoise,
+    }
+}
+
+/// Runs `step` until a state repeats, remembering every state seen. Suits
+/// simulations that may stop, which `step` signals by returning `None`, in
+/// which case there's no cycle.
+pub fn find_cycle_hashed<T: Clone + Eq + Hash>(
+    initial: T,
+    mut step: impl FnMut(&T) -> Option<T>,
+) -> Option<Cycle<T>> {
+    let mut seen = HashMap::new();
+    let mut state = initial;
+
+    for index in 0.. {
+        if let Some(&start) = seen.get(&state) {
+            return Some(Cycle {
+                start,
+                length: index - start,
+                state,
+            });
+        }
+
+        let next = step(&state)?;
+        seen.insert(state, index);
+        state = next;
+    }
+
+    unreachable!()
+}
+
+/// The state after `n` steps from `initial`, skipping whole cycles rather than
+/// simulating every step.
+pub fn nth_state<T: Clone + Eq>(initial: T, mut step: impl FnMut(&T) -> T, n: u64) -> T {
+    let cycle = find_cycle(initial.clone(), &mut step);
+    let (start, length) = (cycle.start as u64, cycle.length as u64);
+
+    let (mut state, remaining) = if n < start {
+        (initial, n)
+    } else {
+        (cycle.state, (n - start) % length)
+    };
+
+    for _ in 0..remaining {
+        state = step(&state);
+    }
+
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 0 -> 1 -> 2 -> 3 -> 4 -> 5 -> 2 -> ...
+    fn rho(state: &u32) -> u32 {
+        if *state == 5 {
+            2
+        } else {
+            state + 1
+        }
+    }
+
+    #[test]
+    fn brent() {
+        assert_eq!(
+            find_cycle(0, rho),
+            Cycle {
+                start: 2,
+                length: 4,
+                state: 2
+            }
+        );
+        assert_eq!(find_cycle(7, |&state| state).length, 1);
+    }
+
+    #[test]
+    fn hashed() {
+        assert_eq!(
+            find_cycle_hashed(0, |state| Some(rho(state))),
+            Some(find_cycle(0, rho))
+        );
+        assert_eq!(
+            find_cycle_hashed(0, |&state| (state < 10).then_some(state + 1)),
+            None
+        );
+    }
+
+    #[test]
+    fn fast_forward() {
+        assert_eq!(nth_state(0, rho, 1), 1);
+        assert_eq!(nth_state(0, rho, 6), 2);
+        assert_eq!(nth_state(0, rho, 1_000_000_000_000), 4);
+        // x -> x^2 + 1 mod 1_000_003
+        let square = |&x: &u64| (x * x + 1) % 1_000_003;
+        let mut expected = 3;
+        for _ in 0..5000 {
+            expected = square(&expected);
+        }
+        assert_eq!(nth_state(3, square, 5000), expected);
+    }
+}
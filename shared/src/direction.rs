@@ -0,0 +1,88 @@
+use crate::{FromByte, Vector};
+
+/// One of the four orthogonal directions on a grid where up is negative `y`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Clockwise from up
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub fn turn_left(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Right => Direction::Up,
+            Direction::Down => Direction::Right,
+            Direction::Left => Direction::Down,
+        }
+    }
+
+    pub fn turn_right(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    pub fn opposite(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Right => Direction::Left,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+        }
+    }
+
+    /// Unit vector for one step in this direction
+    pub fn vector(self) -> Vector {
+        match self {
+            Direction::Up => Vector::new(0, -1),
+            Direction::Right => Vector::new(1, 0),
+            Direction::Down => Vector::new(0, 1),
+            Direction::Left => Vector::new(-1, 0),
+        }
+    }
+
+    pub fn is_vertical(self) -> bool {
+        matches!(self, Direction::Up | Direction::Down)
+    }
+}
+
+/// Parses arrow characters (`^`, `>`, `v`, `<`)
+impl FromByte for Direction {
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            b'^' => Some(Direction::Up),
+            b'>' => Some(Direction::Right),
+            b'v' => Some(Direction::Down),
+            b'<' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turning() {
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(direction.turn_right().turn_right(), direction.opposite());
+            assert_eq!(direction.opposite().vector(), -direction.vector());
+        }
+    }
+}
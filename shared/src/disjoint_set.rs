@@ -0,0 +1,136 @@
+/// Union-find over the elements `0..len`, with path compression and union by
+/// size, so a run of merges and lookups is close to linear.
+#[derive(Debug, Clone)]
+pub struct DisjointSet {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+    component_count: usize,
+}
+
+impl DisjointSet {
+    /// `len` elements, each in a component of its own
+    pub fn new(len: usize) -> DisjointSet {
+        DisjointSet {
+            parents: (0..len).collect(),
+            sizes: vec![1; len],
+            component_count: len,
+        }
+    }
+
+    /// The representative of the component containing `element`
+    pub fn find(&mut self, mut element: usize) -> usize {
+        // Path halving: point every other node on the way up at its grandparent
+        while self.parents[element] != element {
+            let grandparent = self.parents[self.parents[element]];
+            self.parents[element] = grandparent;
+            element = grandparent;
+        }
+
+        element
+    }
+
+    /// Merges the components of `a` and `b`, returning `false` if they were already one
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+
+        if a == b {
+            return false;
+        }
+
+        if self.sizes[a] < self.sizes[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+
+        self.parents[b] = a;
+        self.sizes[a] += self.sizes[b];
+        self.component_count -= 1;
+
+        true
+    }
+
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Number of elements in the component containing `element`
+    pub fn size(&mut self, element: usize) -> usize {
+        let root = self.find(element);
+
+        self.sizes[root]
+    }
+
+    /// Every element in the same component as `element`, in ascending order
+    pub fn members(&mut self, element: usize) -> Vec<usize> {
+        let root = self.find(element);
+
+        (0..self.len())
+            .filter(|&other| self.find(other) == root)
+            .collect()
+    }
+
+    /// All components, each in ascending order, ordered by their smallest element
+    pub fn components(&mut self) -> Vec<Vec<usize>> {
+        let mut indices = vec![None; self.len()];
+        let mut components: Vec<Vec<usize>> = vec![];
+
+        for element in 0..self.len() {
+            let root = self.find(element);
+            let index = *indices[root].get_or_insert_with(|| {
+                components.push(vec![]);
+                components.len() - 1
+            });
+
+            components[index].push(element);
+        }
+
+        components
+    }
+
+    pub fn component_count(&self) -> usize {
+        self.component_count
+    }
+
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merging() {
+        let mut set = DisjointSet::new(6);
+
+        assert!(set.union(0, 1));
+        assert!(set.union(2, 1));
+        assert!(set.union(4, 5));
+        assert!(!set.union(0, 2));
+
+        assert!(set.connected(0, 2));
+        assert!(!set.connected(0, 3));
+        assert_eq!(set.size(1), 3);
+        assert_eq!(set.size(3), 1);
+        assert_eq!(set.component_count(), 3);
+        assert_eq!(set.members(5), vec![4, 5]);
+        assert_eq!(set.components(), vec![vec![0, 1, 2], vec![3], vec![4, 5]]);
+    }
+
+    #[test]
+    fn long_chain() {
+        let mut set = DisjointSet::new(100_000);
+
+        for element in 1..set.len() {
+            set.union(element - 1, element);
+        }
+
+        assert_eq!(set.component_count(), 1);
+        assert_eq!(set.size(0), 100_000);
+        assert!(set.connected(0, 99_999));
+    }
+}
@@ -0,0 +1,324 @@
+use std::fmt::{self, Display};
+
+/// Builds a Graphviz DOT document, for viewing puzzle structures with `dot`
+/// or any online Graphviz viewer.
+#[derive(Debug, Clone)]
+pub struct Dot {
+    name: String,
+    directed: bool,
+    nodes: Vec<DotNode>,
+    clusters: Vec<DotCluster>,
+    edges: Vec<DotEdge>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DotNode {
+    id: String,
+    label: Option<String>,
+    shape: Option<String>,
+    highlighted: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct DotEdge {
+    from: String,
+    to: String,
+    label: Option<String>,
+    highlighted: bool,
+}
+
+/// A labelled box of nodes that Graphviz lays out together
+#[derive(Debug, Clone)]
+pub struct DotCluster {
+    label: String,
+    nodes: Vec<DotNode>,
+}
+
+impl DotNode {
+    fn new(id: &str) -> DotNode {
+        DotNode {
+            id: id.to_string(),
+            label: None,
+            shape: None,
+            highlighted: false,
+        }
+    }
+
+    /// Text shown in place of the node's ID
+    pub fn label(&mut self, label: impl Display) -> &mut DotNode {
+        self.label = Some(label.to_string());
+        self
+    }
+
+    /// A Graphviz shape, such as `box` or `diamond`
+    pub fn shape(&mut self, shape: &str) -> &mut DotNode {
+        self.shape = Some(shape.to_string());
+        self
+    }
+
+    pub fn highlight(&mut self) -> &mut DotNode {
+        self.highlighted = true;
+        self
+    }
+}
+
+impl DotEdge {
+    pub fn label(&mut self, label: impl Display) -> &mut DotEdge {
+        self.label = Some(label.to_string());
+        self
+    }
+
+    pub fn highlight(&mut self) -> &mut DotEdge {
+        self.highlighted = true;
+        self
+    }
+}
+
+impl DotCluster {
+    /// Adds a node drawn inside this cluster, which edges can refer to by `id`
+    pub fn add_node(&mut self, id: &str) -> &mut DotNode {
+        self.nodes.push(DotNode::new(id));
+        self.nodes.last_mut().unwrap()
+    }
+}
+
+impl Dot {
+    /// A directed graph, with edges drawn as arrows
+    pub fn digraph(name: &str) -> Dot {
+        Dot::new(name, true)
+    }
+
+    /// An undirected graph
+    pub fn graph(name: &str) -> Dot {
+        Dot::new(name, false)
+    }
+
+    fn new(name: &str, directed: bool) -> Dot {
+        Dot {
+            name: name.to_string(),
+            directed,
+            nodes: vec![],
+            clusters: vec![],
+            edges: vec![],
+        }
+    }
+
+    pub fn add_node(&mut self, id: &str) -> &mut DotNode {
+        self.nodes.push(DotNode::new(id));
+        self.nodes.last_mut().unwrap()
+    }
+
+    pub fn add_cluster(&mut self, label: impl Display) -> &mut DotCluster {
+        self.clusters.push(DotCluster {
+            label: label.to_string(),
+            nodes: vec![],
+        });
+        self.clusters.last_mut().unwrap()
+    }
+
+    /// Adds an edge between two node IDs. Nodes that were never added are
+    /// drawn with their ID as the label.
+    pub fn add_edge(&mut self, from: &str, to: &str) -> &mut DotEdge {
+        self.edges.push(DotEdge {
+            from: from.to_string(),
+            to: to.to_string(),
+            label: None,
+            highlighted: false,
+        });
+        self.edges.last_mut().unwrap()
+    }
+
+    /// Highlights the node with this ID wherever it was added, adding it if
+    /// it only appears in edges
+    pub fn highlight_node(&mut self, id: &str) {
+        let mut found = false;
+
+        self.nodes
+            .iter_mut()
+            .chain(
+                self.clusters
+                    .iter_mut()
+                    .flat_map(|cluster| &mut cluster.nodes),
+            )
+            .filter(|node| node.id == id)
+            .for_each(|node| {
+                node.highlighted = true;
+                found = true;
+            });
+
+        if !found {
+            self.add_node(id).highlight();
+        }
+    }
+
+    /// Highlights the edges from `from` to `to`, in either direction for undirected graphs
+    pub fn highlight_edge(&mut self, from: &str, to: &str) {
+        let directed = self.directed;
+
+        self.edges
+            .iter_mut()
+            .filter(|edge| {
+                (edge.from == from && edge.to == to)
+                    || (!directed && edge.from == to && edge.to == from)
+            })
+            .for_each(|edge| edge.highlighted = true);
+    }
+}
+
+/// Quotes `text` as a DOT string
+fn quote(text: &str) -> String {
+    let mut quoted = String::from("\"");
+
+    for character in text.chars() {
+        match character {
+            '"' | '\\' => {
+                quoted.push('\\');
+                quoted.push(character);
+            }
+            '\n' => quoted.push_str("\\n"),
+            _ => quoted.push(character),
+        }
+    }
+
+    quoted.push('"');
+    quoted
+}
+
+fn write_attributes(f: &mut fmt::Formatter<'_>, attributes: &[(&str, String)]) -> fmt::Result {
+    if attributes.is_empty() {
+        return Ok(());
+    }
+
+    let attributes = attributes
+        .iter()
+        .map(|(name, value)| format!("{name}={value}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    write!(f, " [{attributes}]")
+}
+
+fn write_node(f: &mut fmt::Formatter<'_>, node: &DotNode, indent: &str) -> fmt::Result {
+    let mut attributes = vec![];
+
+    if let Some(label) = &node.label {
+        attributes.push(("label", quote(label)));
+    }
+
+    if let Some(shape) = &node.shape {
+        attributes.push(("shape", quote(shape)));
+    }
+
+    if node.highlighted {
+        attributes.push(("style", quote("filled")));
+        attributes.push(("fillcolor", quote("gold")));
+        attributes.push(("color", quote("red")));
+    }
+
+    write!(f, "{indent}{}", quote(&node.id))?;
+    write_attributes(f, &attributes)?;
+    writeln!(f, ";")
+}
+
+impl Display for Dot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (keyword, connector) = if self.directed {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+
+        writeln!(f, "{keyword} {} {{", quote(&self.name))?;
+
+        for node in &self.nodes {
+            write_node(f, node, "    ")?;
+        }
+
+        for (index, cluster) in self.clusters.iter().enumerate() {
+            writeln!(f, "    subgraph cluster_{index} {{")?;
+            writeln!(f, "        label={};", quote(&cluster.label))?;
+
+            for node in &cluster.nodes {
+                write_node(f, node, "        ")?;
+            }
+
+            writeln!(f, "    }}")?;
+        }
+
+        for edge in &self.edges {
+            let mut attributes = vec![];
+
+            if let Some(label) = &edge.label {
+                attributes.push(("label", quote(label)));
+            }
+
+            if edge.highlighted {
+                attributes.push(("color", quote("red")));
+                attributes.push(("penwidth", "2".to_string()));
+            }
+
+            write!(
+                f,
+                "    {} {connector} {}",
+                quote(&edge.from),
+                quote(&edge.to)
+            )?;
+            write_attributes(f, &attributes)?;
+            writeln!(f, ";")?;
+        }
+
+        writeln!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_digraph() {
+        let mut dot = Dot::digraph("tree");
+        dot.add_node("/").label("/ (dir)").shape("box").highlight();
+        dot.add_cluster("files").add_node("a\"b").label("a\"b 10");
+        dot.add_edge("/", "a\"b").label("contains");
+        dot.highlight_edge("/", "a\"b");
+
+        assert_eq!(
+            dot.to_string(),
+            [
+                "digraph \"tree\" {",
+                "    \"/\" [label=\"/ (dir)\", shape=\"box\", style=\"filled\", fillcolor=\"gold\", color=\"red\"];",
+                "    subgraph cluster_0 {",
+                "        label=\"files\";",
+                "        \"a\\\"b\" [label=\"a\\\"b 10\"];",
+                "    }",
+                "    \"/\" -> \"a\\\"b\" [label=\"contains\", color=\"red\", penwidth=2];",
+                "}",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn undirected_highlighting() {
+        let mut dot = Dot::graph("lan");
+        dot.add_edge("ka", "co");
+        dot.add_edge("co", "de");
+        dot.highlight_edge("co", "ka");
+        dot.highlight_node("de");
+
+        assert_eq!(
+            dot.to_string(),
+            [
+                "graph \"lan\" {",
+                "    \"de\" [style=\"filled\", fillcolor=\"gold\", color=\"red\"];",
+                "    \"ka\" -- \"co\" [color=\"red\", penwidth=2];",
+                "    \"co\" -- \"de\";",
+                "}",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+}
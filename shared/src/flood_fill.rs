@@ -0,0 +1,25 @@
+use std::{collections::HashSet, hash::Hash};
+
+/// Every position reachable from `start`, using an explicit stack so large
+/// regions can't overflow the call stack. Works for any position type.
+pub fn flood_fill<P, I>(start: P, mut neighbours: impl FnMut(&P) -> I) -> Vec<P>
+where
+    P: Clone + Eq + Hash,
+    I: IntoIterator<Item = P>,
+{
+    let mut visited = HashSet::from([start.clone()]);
+    let mut stack = vec![start];
+    let mut result = vec![];
+
+    while let Some(position) = stack.pop() {
+        for neighbour in neighbours(&position) {
+            if visited.insert(neighbour.clone()) {
+                stack.push(neighbour);
+            }
+        }
+
+        result.push(position);
+    }
+
+    result
+}
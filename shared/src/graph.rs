@@ -0,0 +1,435 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::Dot;
+
+/// A directed graph stored as adjacency sets. Node IDs are ordered so that
+/// every traversal, and therefore every answer, is deterministic.
+///
+/// Undirected graphs are modelled by adding each edge in both directions with
+/// [`Graph::add_undirected_edge`].
+#[derive(Debug, Clone)]
+pub struct Graph<N> {
+    edges: BTreeMap<N, BTreeSet<N>>,
+}
+
+impl<N: Clone + Ord> Default for Graph<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N: Clone + Ord> Graph<N> {
+    pub fn new() -> Self {
+        Self {
+            edges: BTreeMap::new(),
+        }
+    }
+
+    pub fn add_node(&mut self, node: N) {
+        self.edges.entry(node).or_default();
+    }
+
+    pub fn add_edge(&mut self, from: N, to: N) {
+        self.add_node(to.clone());
+        self.edges.entry(from).or_default().insert(to);
+    }
+
+    pub fn add_undirected_edge(&mut self, a: N, b: N) {
+        self.add_edge(a.clone(), b.clone());
+        self.add_edge(b, a);
+    }
+
+    pub fn has_edge(&self, from: &N, to: &N) -> bool {
+        self.edges.get(from).is_some_and(|edges| edges.contains(to))
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = &N> {
+        self.edges.keys()
+    }
+
+    pub fn neighbours(&self, node: &N) -> impl Iterator<Item = &N> {
+        self.edges.get(node).into_iter().flatten()
+    }
+
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    /// Every node ordered so that edges only point forwards, or `None` if
+    /// there's a cycle
+    pub fn topological_sort(&self) -> Option<Vec<N>> {
+        self.topological_sort_of(self.nodes().cloned())
+    }
+
+    /// Orders just `nodes`, considering only the edges between them, so a
+    /// subset can be sorted even when the whole graph has cycles. Ties are
+    /// broken by node order.
+    pub fn topological_sort_of(&self, nodes: impl IntoIterator<Item = N>) -> Option<Vec<N>> {
+        let subset = nodes.into_iter().collect::<BTreeSet<_>>();
+        let mut in_degrees = subset
+            .iter()
+            .map(|node| (node, 0))
+            .collect::<BTreeMap<_, _>>();
+
+        for node in &subset {
+            for next in self.neighbours(node) {
+                if let Some(in_degree) = in_degrees.get_mut(next) {
+                    *in_degree += 1;
+                }
+            }
+        }
+
+        let mut ready = in_degrees
+            .iter()
+            .filter(|(_, &in_degree)| in_degree == 0)
+            .map(|(&node, _)| node)
+            .collect::<BTreeSet<_>>();
+        let mut result = Vec::with_capacity(subset.len());
+
+        while let Some(node) = ready.pop_first() {
+            result.push(node.clone());
+
+            for next in self.neighbours(node) {
+                if let Some(in_degree) = in_degrees.get_mut(next) {
+                    *in_degree -= 1;
+
+                    if *in_degree == 0 {
+                        ready.insert(next);
+                    }
+                }
+            }
+        }
+
+        (result.len() == subset.len()).then_some(result)
+    }
+
+    /// Tarjan's strongly connected components, each listed before any
+    /// component that can reach it
+    pub fn strongly_connected_components(&self) -> Vec<Vec<N>> {
+        let (nodes, adjacency) = self.indexed();
+        let mut index = vec![usize::MAX; nodes.len()];
+        let mut low_link = vec![0; nodes.len()];
+        let mut on_stack = vec![false; nodes.len()];
+        let mut stack = vec![];
+        let mut counter = 0;
+        let mut components = vec![];
+
+        for root in 0..nodes.len() {
+            if index[root] != usize::MAX {
+                continue;
+            }
+
+            // (node, next edge to follow) stands in for the recursive call stack
+            let mut calls = vec![(root, 0)];
+            index[root] = counter;
+            low_link[root] = counter;
+            counter += 1;
+            stack.push(root);
+            on_stack[root] = true;
+
+            while let Some((node, edge)) = calls.last_mut() {
+                let node = *node;
+
+                if let Some(&next) = adjacency[node].get(*edge) {
+                    *edge += 1;
+
+                    if index[next] == usize::MAX {
+                        index[next] = counter;
+                        low_link[next] = counter;
+                        counter += 1;
+                        stack.push(next);
+                        on_stack[next] = true;
+                        calls.push((next, 0));
+                    } else if on_stack[next] {
+                        low_link[node] = low_link[node].min(index[next]);
+                    }
+
+                    continue;
+                }
+
+                calls.pop();
+
+                if let Some(&(parent, _)) = calls.last() {
+                    low_link[parent] = low_link[parent].min(low_link[node]);
+                }
+
+                if low_link[node] == index[node] {
+                    let mut component = vec![];
+
+                    while let Some(member) = stack.pop() {
+                        on_stack[member] = false;
+                        component.push(nodes[member].clone());
+
+                        if member == node {
+                            break;
+                        }
+                    }
+
+                    components.push(component);
+                }
+            }
+        }
+
+        components
+    }
+
+    /// Every clique that can't be extended by another node, found with
+    /// Bron–Kerbosch. Edges are treated as undirected, so an edge in either
+    /// direction connects two nodes, and self-loops are ignored.
+    pub fn maximal_cliques(&self) -> Vec<Vec<N>> {
+        let (nodes, adjacency) = self.undirected_indexed();
+        let mut cliques = vec![];
+
+        bron_kerbosch(
+            &adjacency,
+            &mut vec![],
+            (0..nodes.len()).collect(),
+            BTreeSet::new(),
+            &mut cliques,
+        );
+
+        cliques
+            .into_iter()
+            .map(|clique| clique.into_iter().map(|node| nodes[node].clone()).collect())
+            .collect()
+    }
+
+    /// The largest clique, sorted by node
+    pub fn maximum_clique(&self) -> Vec<N> {
+        let mut clique = self
+            .maximal_cliques()
+            .into_iter()
+            .max_by_key(|clique| clique.len())
+            .unwrap_or_default();
+
+        clique.sort();
+        clique
+    }
+
+    /// Every set of three mutually connected nodes, each listed once in order.
+    /// Edges are treated as undirected, as in [`Graph::maximal_cliques`].
+    pub fn triangles(&self) -> Vec<[N; 3]> {
+        let (nodes, adjacency) = self.undirected_indexed();
+        let mut result = vec![];
+
+        // Indices follow node order, so a < b < c lists each triangle once in order
+        for (a, a_neighbours) in adjacency.iter().enumerate() {
+            for &b in a_neighbours.range(a + 1..) {
+                for &c in adjacency[b].range(b + 1..) {
+                    if a_neighbours.contains(&c) {
+                        result.push([nodes[a].clone(), nodes[b].clone(), nodes[c].clone()]);
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
+    /// A DOT digraph of every node and edge, identifying nodes by `name`
+    pub fn to_dot(&self, title: &str, name: impl Fn(&N) -> String) -> Dot {
+        self.write_dot(Dot::digraph(title), name, |_, _| true)
+    }
+
+    /// A DOT graph drawing each pair of opposite edges as one undirected edge
+    pub fn to_undirected_dot(&self, title: &str, name: impl Fn(&N) -> String) -> Dot {
+        self.write_dot(Dot::graph(title), name, |from, to| {
+            from <= to || !self.has_edge(to, from)
+        })
+    }
+
+    fn write_dot(
+        &self,
+        mut dot: Dot,
+        name: impl Fn(&N) -> String,
+        include_edge: impl Fn(&N, &N) -> bool,
+    ) -> Dot {
+        for node in self.nodes() {
+            dot.add_node(&name(node));
+        }
+
+        for from in self.nodes() {
+            for to in self.neighbours(from).filter(|&to| include_edge(from, to)) {
+                dot.add_edge(&name(from), &name(to));
+            }
+        }
+
+        dot
+    }
+
+    /// Nodes in order alongside adjacency lists of their indices
+    /// Like [`Graph::indexed`], but with every edge in both directions and no self-loops
+    fn undirected_indexed(&self) -> (Vec<N>, Vec<BTreeSet<usize>>) {
+        let (nodes, edges) = self.indexed();
+        let mut adjacency = vec![BTreeSet::new(); nodes.len()];
+
+        for (from, targets) in edges.into_iter().enumerate() {
+            for to in targets.into_iter().filter(|&to| to != from) {
+                adjacency[from].insert(to);
+                adjacency[to].insert(from);
+            }
+        }
+
+        (nodes, adjacency)
+    }
+
+    fn indexed(&self) -> (Vec<N>, Vec<Vec<usize>>) {
+        let nodes = self.nodes().cloned().collect::<Vec<_>>();
+        let indices = nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (node, index))
+            .collect::<BTreeMap<_, _>>();
+        let adjacency = nodes
+            .iter()
+            .map(|node| self.neighbours(node).map(|next| indices[next]).collect())
+            .collect();
+
+        (nodes, adjacency)
+    }
+}
+
+fn bron_kerbosch(
+    adjacency: &[BTreeSet<usize>],
+    clique: &mut Vec<usize>,
+    mut candidates: BTreeSet<usize>,
+    mut excluded: BTreeSet<usize>,
+    cliques: &mut Vec<Vec<usize>>,
+) {
+    if candidates.is_empty() {
+        if excluded.is_empty() {
+            cliques.push(clique.clone());
+        }
+
+        return;
+    }
+
+    // Any maximal clique contains the pivot or one of its non-neighbours
+    let pivot = candidates
+        .union(&excluded)
+        .max_by_key(|&&node| adjacency[node].intersection(&candidates).count())
+        .copied()
+        .unwrap();
+    let branches = candidates
+        .difference(&adjacency[pivot])
+        .copied()
+        .collect::<Vec<_>>();
+
+    for node in branches {
+        clique.push(node);
+        bron_kerbosch(
+            adjacency,
+            clique,
+            candidates.intersection(&adjacency[node]).copied().collect(),
+            excluded.intersection(&adjacency[node]).copied().collect(),
+            cliques,
+        );
+        clique.pop();
+
+        candidates.remove(&node);
+        excluded.insert(node);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn undirected(edges: &[(u32, u32)]) -> Graph<u32> {
+        let mut graph = Graph::new();
+
+        for &(a, b) in edges {
+            graph.add_undirected_edge(a, b);
+        }
+
+        graph
+    }
+
+    #[test]
+    fn topological_sort() {
+        let mut graph = Graph::new();
+        graph.add_edge(3, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 4);
+        graph.add_edge(4, 3);
+
+        assert_eq!(graph.topological_sort(), None);
+        assert_eq!(graph.topological_sort_of([1, 2, 3]), Some(vec![3, 1, 2]));
+        assert_eq!(graph.topological_sort_of([4, 1]), Some(vec![1, 4]));
+    }
+
+    #[test]
+    fn strongly_connected_components() {
+        let mut graph = Graph::new();
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 1);
+        graph.add_edge(2, 3);
+        graph.add_edge(3, 4);
+        graph.add_edge(4, 3);
+        graph.add_node(5);
+
+        let mut components = graph.strongly_connected_components();
+        components.iter_mut().for_each(|component| component.sort());
+
+        assert_eq!(components, vec![vec![3, 4], vec![1, 2], vec![5]]);
+    }
+
+    #[test]
+    fn cliques_and_triangles() {
+        // A 4-clique {1, 2, 3, 4} with a tail 4 - 5 - 6
+        let graph = undirected(&[
+            (1, 2),
+            (1, 3),
+            (1, 4),
+            (2, 3),
+            (2, 4),
+            (3, 4),
+            (4, 5),
+            (5, 6),
+        ]);
+
+        assert_eq!(graph.maximum_clique(), vec![1, 2, 3, 4]);
+        assert_eq!(graph.maximal_cliques().len(), 3);
+        assert_eq!(
+            graph.triangles(),
+            vec![[1, 2, 3], [1, 2, 4], [1, 3, 4], [2, 3, 4]]
+        );
+    }
+
+    #[test]
+    fn cliques_of_directed_edges() {
+        // A triangle whose edges only go one way, with a self-loop on 1
+        let mut graph = Graph::new();
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+        graph.add_edge(3, 1);
+        graph.add_edge(1, 1);
+
+        assert_eq!(graph.maximal_cliques().len(), 1);
+        assert_eq!(graph.maximum_clique(), vec![1, 2, 3]);
+        assert_eq!(graph.triangles(), vec![[1, 2, 3]]);
+    }
+
+    #[test]
+    fn dot_export() {
+        let mut graph = undirected(&[(1, 2)]);
+        graph.add_edge(2, 3);
+
+        assert_eq!(
+            graph.to_undirected_dot("g", u32::to_string).to_string(),
+            "graph \"g\" {\n    \"1\";\n    \"2\";\n    \"3\";\n    \"1\" -- \"2\";\n    \"2\" -- \"3\";\n}\n"
+        );
+        assert_eq!(
+            graph
+                .to_dot("g", u32::to_string)
+                .to_string()
+                .matches(" -> ")
+                .count(),
+            3
+        );
+    }
+}
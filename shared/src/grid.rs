@@ -1,8 +1,114 @@
-use std::{fmt::Debug, str::FromStr};
+use std::{error::Error, fmt::Debug, fmt::Display, str::FromStr};
 
-use crate::Coordinate;
+use crate::{Coordinate, Point, Torus, Vector};
 
-#[derive(Debug)]
+mod distance;
+mod regions;
+mod transform;
+
+pub use distance::{DistanceField, Shortcut};
+pub use regions::Regions;
+pub use transform::SubGrid;
+
+/// Maps a single input byte to a grid cell, returning `None` for bytes that
+/// don't describe a valid cell.
+pub trait FromByte: Sized {
+    fn from_byte(byte: u8) -> Option<Self>;
+}
+
+impl FromByte for u8 {
+    fn from_byte(byte: u8) -> Option<Self> {
+        Some(byte)
+    }
+}
+
+/// Only ASCII bytes map to a `char`; any other byte is part of a multi-byte
+/// UTF-8 sequence and can't stand for a cell on its own
+impl FromByte for char {
+    fn from_byte(byte: u8) -> Option<Self> {
+        byte.is_ascii().then_some(byte as char)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridError {
+    Empty,
+    RaggedRow {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    InvalidCell {
+        position: Coordinate,
+        byte: u8,
+    },
+}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridError::Empty => write!(f, "grid input is empty"),
+            GridError::RaggedRow {
+                row,
+                expected,
+                found,
+            } => write!(f, "row {row} has {found} cells, expected {expected}"),
+            GridError::InvalidCell { position, byte } => write!(
+                f,
+                "invalid cell {:?} at row {}, col {}",
+                *byte as char,
+                position.row(),
+                position.col()
+            ),
+        }
+    }
+}
+
+impl Error for GridError {}
+
+/// Drops leading and trailing line breaks but keeps spaces, which can be cells
+fn trim_blank_lines(input: &str) -> &str {
+    input.trim_matches(['\n', '\r'])
+}
+
+/// Neighbour lookup and rendering shared by the dense and sparse grids.
+pub trait GridView {
+    type Position;
+    type Cell;
+
+    fn cell(&self, position: &Self::Position) -> Option<&Self::Cell>;
+
+    /// The up, down, left and right neighbours of `position` that are in the grid
+    fn neighbours(&self, position: &Self::Position) -> Vec<Self::Position>;
+
+    /// All eight neighbours of `position`, including diagonals, that are in the grid
+    fn all_neighbours(&self, position: &Self::Position) -> Vec<Self::Position>;
+
+    /// Renders one line per row, converting each cell with `to_char`
+    fn render(&self, to_char: impl Fn(&Self::Cell) -> char) -> String;
+}
+
+/// Positions of marker bytes (e.g. `S` and `E`) found while parsing a grid.
+#[derive(Debug, Default)]
+pub struct Markers {
+    positions: Vec<(u8, Coordinate)>,
+}
+
+impl Markers {
+    /// The first position of `marker`, in row-major order.
+    pub fn get(&self, marker: u8) -> Option<&Coordinate> {
+        self.all(marker).next()
+    }
+
+    pub fn all(&self, marker: u8) -> impl Iterator<Item = &Coordinate> {
+        self.positions
+            .iter()
+            .filter(move |(byte, _)| *byte == marker)
+            .map(|(_, position)| position)
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct Grid<TCell> {
     data: Vec<TCell>,
     row_count: usize,
@@ -25,7 +131,7 @@ impl<TCell: FromStr> Grid<TCell> {
         let mut row_count = 0;
         let mut col_count = 0;
 
-        for line in input.trim().lines() {
+        for line in trim_blank_lines(input).lines() {
             if col_count == 0 {
                 col_count = line.len();
             }
@@ -45,9 +151,121 @@ impl<TCell: FromStr> Grid<TCell> {
     }
 }
 
+impl<TCell: FromByte> Grid<TCell> {
+    /// Parses a grid one byte per cell. Every row must be the same length.
+    pub fn parse(input: &str) -> Result<Grid<TCell>, GridError> {
+        Self::parse_with_markers(input, &[]).map(|(grid, _)| grid)
+    }
+
+    /// Like [`Grid::parse`], but records the position of each `(marker, replacement)`
+    /// byte and parses the cell as `replacement` instead.
+    pub fn parse_with_markers(
+        input: &str,
+        markers: &[(u8, u8)],
+    ) -> Result<(Grid<TCell>, Markers), GridError> {
+        let input = trim_blank_lines(input);
+        let mut data = Vec::with_capacity(input.len());
+        let mut found_markers = Markers::default();
+        let mut row_count = 0;
+        let mut col_count = 0;
+
+        for (row, line) in input.lines().enumerate() {
+            let bytes = line.as_bytes();
+
+            if row == 0 {
+                col_count = bytes.len();
+            } else if bytes.len() != col_count {
+                return Err(GridError::RaggedRow {
+                    row,
+                    expected: col_count,
+                    found: bytes.len(),
+                });
+            }
+
+            for (col, &byte) in bytes.iter().enumerate() {
+                let mut byte = byte;
+
+                if let Some(&(marker, replacement)) =
+                    markers.iter().find(|(marker, _)| *marker == byte)
+                {
+                    found_markers
+                        .positions
+                        .push((marker, Coordinate::new(row, col)));
+                    byte = replacement;
+                }
+
+                match TCell::from_byte(byte) {
+                    Some(cell) => data.push(cell),
+                    None => {
+                        return Err(GridError::InvalidCell {
+                            position: Coordinate::new(row, col),
+                            byte,
+                        })
+                    }
+                }
+            }
+
+            row_count += 1;
+        }
+
+        if data.is_empty() {
+            return Err(GridError::Empty);
+        }
+
+        Ok((
+            Grid {
+                data,
+                row_count,
+                col_count,
+            },
+            found_markers,
+        ))
+    }
+}
+
 impl<TCell> Grid<TCell> {
+    /// Builds a grid by calling `cell` for every position in row-major order
+    pub fn from_fn(
+        row_count: usize,
+        col_count: usize,
+        mut cell: impl FnMut(Coordinate) -> TCell,
+    ) -> Grid<TCell> {
+        let mut data = Vec::with_capacity(row_count * col_count);
+
+        for row in 0..row_count {
+            for col in 0..col_count {
+                data.push(cell(Coordinate::new(row, col)));
+            }
+        }
+
+        Grid {
+            data,
+            row_count,
+            col_count,
+        }
+    }
+
+    pub fn row_count(&self) -> usize {
+        self.row_count
+    }
+
+    pub fn col_count(&self) -> usize {
+        self.col_count
+    }
+
     pub fn get(&self, position: &Coordinate) -> &TCell {
-        &self.data[self.get_index(position)]
+        &self.data[self.index_of(position)]
+    }
+
+    pub fn get_mut(&mut self, position: &Coordinate) -> &mut TCell {
+        let index = self.index_of(position);
+
+        &mut self.data[index]
+    }
+
+    /// Each row as a slice. A grid with no columns still has `row_count` empty rows.
+    pub fn rows(&self) -> impl Iterator<Item = &[TCell]> {
+        (0..self.row_count).map(|row| &self.data[row * self.col_count..(row + 1) * self.col_count])
     }
 
     pub fn get_cursor(&self, position: &Coordinate) -> GridCursor<'_, TCell> {
@@ -57,7 +275,41 @@ impl<TCell> Grid<TCell> {
         }
     }
 
-    fn get_index(&self, position: &Coordinate) -> usize {
+    /// Moves `position` by the given deltas, returning `None` if it leaves the grid
+    pub fn offset(
+        &self,
+        position: &Coordinate,
+        row_delta: isize,
+        col_delta: isize,
+    ) -> Option<Coordinate> {
+        let row = position.row().checked_add_signed(row_delta)?;
+        let col = position.col().checked_add_signed(col_delta)?;
+        let next_position = Coordinate::new(row, col);
+
+        if self.contains_position(&next_position) {
+            Some(next_position)
+        } else {
+            None
+        }
+    }
+
+    /// Moves `position` by `velocity` `steps` times, wrapping around the grid edges
+    pub fn wrapping_offset(
+        &self,
+        position: &Coordinate,
+        velocity: Vector,
+        steps: i64,
+    ) -> Coordinate {
+        let torus = Torus::new(self.col_count as i64, self.row_count as i64);
+        let start = Point::new(position.col() as i64, position.row() as i64);
+        let end = torus.step_n(start, velocity, steps);
+
+        Coordinate::new(end.y as usize, end.x as usize)
+    }
+
+    /// Position of `position` in the grid's row-major cell order, as used by
+    /// index-based structures such as a [`crate::DisjointSet`] over the cells
+    pub fn index_of(&self, position: &Coordinate) -> usize {
         let index = position.row() * self.col_count + position.col();
 
         if index >= self.data.len() {
@@ -67,11 +319,64 @@ impl<TCell> Grid<TCell> {
         index
     }
 
+    fn coordinate(&self, index: usize) -> Coordinate {
+        Coordinate::new(index / self.col_count, index % self.col_count)
+    }
+
     fn contains_position(&self, position: &Coordinate) -> bool {
         position.row() < self.row_count && position.col() < self.col_count
     }
 }
 
+impl<TCell> GridView for Grid<TCell> {
+    type Position = Coordinate;
+    type Cell = TCell;
+
+    fn cell(&self, position: &Coordinate) -> Option<&TCell> {
+        if self.contains_position(position) {
+            Some(self.get(position))
+        } else {
+            None
+        }
+    }
+
+    fn neighbours(&self, position: &Coordinate) -> Vec<Coordinate> {
+        [(-1, 0), (1, 0), (0, -1), (0, 1)]
+            .into_iter()
+            .filter_map(|(row_delta, col_delta)| self.offset(position, row_delta, col_delta))
+            .collect()
+    }
+
+    fn all_neighbours(&self, position: &Coordinate) -> Vec<Coordinate> {
+        let mut result = vec![];
+
+        for row_delta in -1..=1 {
+            for col_delta in -1..=1 {
+                if row_delta == 0 && col_delta == 0 {
+                    continue;
+                }
+
+                if let Some(neighbour) = self.offset(position, row_delta, col_delta) {
+                    result.push(neighbour);
+                }
+            }
+        }
+
+        result
+    }
+
+    fn render(&self, to_char: impl Fn(&TCell) -> char) -> String {
+        let mut result = String::with_capacity(self.row_count * (self.col_count + 1));
+
+        for row in self.rows() {
+            result.extend(row.iter().map(&to_char));
+            result.push('\n');
+        }
+
+        result
+    }
+}
+
 impl<TCell> GridCursor<'_, TCell> {
     pub fn down(&mut self) -> bool {
         let next_pos = self.position.down();
@@ -148,3 +453,101 @@ impl<TCell> Clone for GridCursor<'_, TCell> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_grid() {
+        let grid = Grid::<u8>::parse("ab\ncd\n").unwrap();
+
+        assert_eq!(grid.row_count(), 2);
+        assert_eq!(grid.col_count(), 2);
+        assert_eq!(*grid.get(&Coordinate::new(1, 0)), b'c');
+        assert_eq!(grid.index_of(&Coordinate::new(1, 0)), 2);
+    }
+
+    #[test]
+    fn parse_ragged_grid() {
+        let result = Grid::<u8>::parse("abc\nab\nabc");
+
+        assert_eq!(
+            result.unwrap_err(),
+            GridError::RaggedRow {
+                row: 1,
+                expected: 3,
+                found: 2
+            }
+        );
+    }
+
+    #[test]
+    fn neighbours_stay_in_bounds() {
+        let grid = Grid::<u8>::parse("abc\ndef").unwrap();
+
+        assert_eq!(
+            grid.neighbours(&Coordinate::new(0, 0)),
+            vec![Coordinate::new(1, 0), Coordinate::new(0, 1)]
+        );
+        assert_eq!(grid.all_neighbours(&Coordinate::new(1, 1)).len(), 5);
+        assert_eq!(
+            grid.render(|&cell| cell.to_ascii_uppercase() as char),
+            "ABC\nDEF\n"
+        );
+    }
+
+    #[test]
+    fn wrapping_offset() {
+        let grid = Grid::<u8>::parse("....\n....\n....").unwrap();
+
+        assert_eq!(
+            grid.wrapping_offset(&Coordinate::new(0, 0), Vector::new(-1, -1), 1),
+            Coordinate::new(2, 3)
+        );
+        assert_eq!(
+            grid.wrapping_offset(&Coordinate::new(1, 1), Vector::new(3, 2), 1_000_001),
+            Coordinate::new(2, 0)
+        );
+    }
+
+    #[test]
+    fn parse_keeps_leading_spaces() {
+        let (grid, markers) =
+            Grid::<char>::parse_with_markers("\n  S\n.. \n", &[(b'S', b' ')]).unwrap();
+
+        assert_eq!(grid.render(|&cell| cell), "   \n.. \n");
+        assert_eq!(markers.get(b'S'), Some(&Coordinate::new(0, 2)));
+    }
+
+    #[test]
+    fn char_cells_reject_non_ascii() {
+        let result = Grid::<char>::parse("aé");
+
+        assert_eq!(
+            result.unwrap_err(),
+            GridError::InvalidCell {
+                position: Coordinate::new(0, 1),
+                byte: 0xc3
+            }
+        );
+    }
+
+    #[test]
+    fn no_columns() {
+        let grid = Grid::from_fn(2, 0, |_| 0u8);
+
+        assert_eq!(grid.rows().collect::<Vec<_>>(), vec![&[] as &[u8], &[]]);
+        assert_eq!(grid.render(|_| '#'), "\n\n");
+    }
+
+    #[test]
+    fn parse_markers() {
+        let (grid, markers) =
+            Grid::<u8>::parse_with_markers("#S.\n..E", &[(b'S', b'.'), (b'E', b'.')]).unwrap();
+
+        assert_eq!(markers.get(b'S'), Some(&Coordinate::new(0, 1)));
+        assert_eq!(markers.get(b'E'), Some(&Coordinate::new(1, 2)));
+        assert_eq!(*grid.get(&Coordinate::new(0, 1)), b'.');
+    }
+}
@@ -0,0 +1,152 @@
+use crate::{bfs, Coordinate, Grid, GridView};
+
+/// Shortest step counts over a grid from the nearest of one or more sources.
+#[derive(Debug, Clone)]
+pub struct DistanceField {
+    distances: Grid<Option<usize>>,
+}
+
+/// A jump between two cells of a [`DistanceField`] that skips part of the walk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Shortcut {
+    pub from: Coordinate,
+    pub to: Coordinate,
+    /// Steps saved compared to walking from `from` to `to`
+    pub saving: usize,
+}
+
+impl<TCell> Grid<TCell> {
+    /// Breadth-first distances from `sources` through orthogonal moves onto
+    /// `passable` cells
+    pub fn distance_field(
+        &self,
+        sources: impl IntoIterator<Item = Coordinate>,
+        passable: impl Fn(&TCell) -> bool,
+    ) -> DistanceField {
+        let search = bfs(
+            sources,
+            |position| {
+                self.neighbours(position)
+                    .into_iter()
+                    .filter(|neighbour| passable(self.get(neighbour)))
+                    .collect::<Vec<_>>()
+            },
+            |_| false,
+        );
+        let mut distances = Grid::from_fn(self.row_count, self.col_count, |_| None);
+
+        for (position, distance) in search.distances() {
+            *distances.get_mut(position) = Some(distance);
+        }
+
+        DistanceField { distances }
+    }
+
+    /// Cells within Manhattan distance `radius` of `position` (excluding itself),
+    /// paired with that distance
+    pub fn within_manhattan_distance(
+        &self,
+        position: &Coordinate,
+        radius: usize,
+    ) -> impl Iterator<Item = (Coordinate, usize)> + '_ {
+        let position = position.clone();
+        let radius = radius as isize;
+
+        (-radius..=radius).flat_map(move |row_delta| {
+            let remaining = radius - row_delta.abs();
+            let position = position.clone();
+
+            (-remaining..=remaining).filter_map(move |col_delta| {
+                if row_delta == 0 && col_delta == 0 {
+                    return None;
+                }
+
+                self.offset(&position, row_delta, col_delta)
+                    .map(|other| (other, (row_delta.abs() + col_delta.abs()) as usize))
+            })
+        })
+    }
+}
+
+impl DistanceField {
+    /// Distance to `position`, or `None` if it can't be reached
+    pub fn get(&self, position: &Coordinate) -> Option<usize> {
+        *self.distances.get(position)
+    }
+
+    pub fn reachable(&self) -> impl Iterator<Item = (Coordinate, usize)> + '_ {
+        self.distances
+            .data
+            .iter()
+            .enumerate()
+            .filter_map(|(index, distance)| distance.map(|d| (self.distances.coordinate(index), d)))
+    }
+
+    /// Every jump of at most `radius` steps (Manhattan) between reachable cells
+    /// that arrives further along the field than walking would
+    pub fn shortcuts(&self, radius: usize) -> impl Iterator<Item = Shortcut> + '_ {
+        self.reachable().flat_map(move |(from, from_distance)| {
+            self.distances
+                .within_manhattan_distance(&from, radius)
+                .filter_map(move |(to, jump)| {
+                    let to_distance = self.get(&to)?;
+                    let saving = to_distance.checked_sub(from_distance + jump)?;
+
+                    (saving > 0).then(|| Shortcut {
+                        from: from.clone(),
+                        to,
+                        saving,
+                    })
+                })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distance_field() {
+        let grid = Grid::<u8>::parse("...\n##.\n...").unwrap();
+        let field = grid.distance_field([Coordinate::new(0, 0)], |&cell| cell == b'.');
+
+        assert_eq!(field.get(&Coordinate::new(2, 0)), Some(6));
+        assert_eq!(field.get(&Coordinate::new(1, 0)), None);
+        assert_eq!(field.reachable().count(), 7);
+    }
+
+    #[test]
+    fn shortcuts_through_walls() {
+        let grid = Grid::<u8>::parse("...\n##.\n...").unwrap();
+        let field = grid.distance_field([Coordinate::new(0, 0)], |&cell| cell == b'.');
+        let shortcuts = field.shortcuts(2).collect::<Vec<_>>();
+
+        // Straight through the wall from the top-left corner to the bottom-left
+        assert!(shortcuts.contains(&Shortcut {
+            from: Coordinate::new(0, 0),
+            to: Coordinate::new(2, 0),
+            saving: 4,
+        }));
+        assert_eq!(
+            shortcuts.iter().map(|shortcut| shortcut.saving).max(),
+            Some(4)
+        );
+    }
+
+    #[test]
+    fn within_manhattan_distance() {
+        let grid = Grid::from_fn(5, 5, |_| 0);
+
+        assert_eq!(
+            grid.within_manhattan_distance(&Coordinate::new(2, 2), 2)
+                .count(),
+            12
+        );
+        assert_eq!(
+            grid.within_manhattan_distance(&Coordinate::new(0, 0), 1)
+                .count(),
+            2
+        );
+    }
+}
@@ -0,0 +1,188 @@
+use crate::{flood_fill, Coordinate, Grid, GridView};
+
+const DIAGONALS: [(isize, isize); 4] = [(-1, -1), (-1, 1), (1, -1), (1, 1)];
+
+/// Connected regions of a grid, with every cell labelled by its region index.
+#[derive(Debug, Clone)]
+pub struct Regions {
+    labels: Grid<usize>,
+    regions: Vec<Vec<Coordinate>>,
+}
+
+impl<TCell> Grid<TCell> {
+    /// Cells reachable from `start` through orthogonal neighbours where
+    /// `connected(from, to)` holds
+    pub fn flood_fill(
+        &self,
+        start: &Coordinate,
+        mut connected: impl FnMut(&TCell, &TCell) -> bool,
+    ) -> Vec<Coordinate> {
+        flood_fill(start.clone(), |position| {
+            let cell = self.get(position);
+
+            self.neighbours(position)
+                .into_iter()
+                .filter(|neighbour| connected(cell, self.get(neighbour)))
+                .collect::<Vec<_>>()
+        })
+    }
+
+    /// Splits the grid into regions, where neighbouring cells share a region
+    /// when `connected(from, to)` holds
+    pub fn connected_components(
+        &self,
+        mut connected: impl FnMut(&TCell, &TCell) -> bool,
+    ) -> Regions {
+        let mut labels = Grid::from_fn(self.row_count, self.col_count, |_| usize::MAX);
+        let mut regions = vec![];
+
+        for start in 0..self.data.len() {
+            if labels.data[start] != usize::MAX {
+                // Already placed into a region
+                continue;
+            }
+
+            let label = regions.len();
+            let mut region = vec![];
+            let mut stack = vec![self.coordinate(start)];
+            labels.data[start] = label;
+
+            while let Some(position) = stack.pop() {
+                let cell = self.get(&position);
+
+                for neighbour in self.neighbours(&position) {
+                    let index = self.index_of(&neighbour);
+
+                    if labels.data[index] == usize::MAX && connected(cell, &self.data[index]) {
+                        labels.data[index] = label;
+                        stack.push(neighbour);
+                    }
+                }
+
+                region.push(position);
+            }
+
+            regions.push(region);
+        }
+
+        Regions { labels, regions }
+    }
+}
+
+impl Regions {
+    pub fn len(&self) -> usize {
+        self.regions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+
+    /// The index of the region containing `position`
+    pub fn label(&self, position: &Coordinate) -> usize {
+        *self.labels.get(position)
+    }
+
+    pub fn cells(&self, region: usize) -> &[Coordinate] {
+        &self.regions[region]
+    }
+
+    pub fn area(&self, region: usize) -> usize {
+        self.regions[region].len()
+    }
+
+    /// Number of cell edges that border another region or the edge of the grid
+    pub fn perimeter(&self, region: usize) -> usize {
+        self.regions[region]
+            .iter()
+            .map(|position| {
+                let same_region_neighbours = self
+                    .labels
+                    .neighbours(position)
+                    .iter()
+                    .filter(|neighbour| *self.labels.get(neighbour) == region)
+                    .count();
+
+                4 - same_region_neighbours
+            })
+            .sum()
+    }
+
+    /// Number of straight fence sides, which is the same as the number of corners
+    pub fn sides(&self, region: usize) -> usize {
+        self.regions[region]
+            .iter()
+            .map(|position| self.corners(position))
+            .sum()
+    }
+
+    /// Number of region corners (inner and outer) touching the cell at `position`
+    pub fn corners(&self, position: &Coordinate) -> usize {
+        let region = self.label(position);
+        let in_region = |row_delta, col_delta| {
+            self.labels
+                .offset(position, row_delta, col_delta)
+                .is_some_and(|neighbour| *self.labels.get(&neighbour) == region)
+        };
+
+        DIAGONALS
+            .iter()
+            .filter(|&&(row_delta, col_delta)| {
+                let vertical = in_region(row_delta, 0);
+                let horizontal = in_region(0, col_delta);
+
+                // Outer corner, or inner corner with the diagonal cut out
+                (!vertical && !horizontal)
+                    || (vertical && horizontal && !in_region(row_delta, col_delta))
+            })
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn region_metrics() {
+        let grid = Grid::<u8>::parse("AAAA\nBBCD\nBBCC\nEEEC").unwrap();
+        let regions = grid.connected_components(|a, b| a == b);
+
+        let c = regions.label(&Coordinate::new(1, 2));
+
+        assert_eq!(regions.len(), 5);
+        assert_eq!(regions.area(c), 4);
+        assert_eq!(regions.perimeter(c), 10);
+        assert_eq!(regions.sides(c), 8);
+        assert_eq!(
+            (0..regions.len())
+                .map(|region| regions.area(region) * regions.perimeter(region))
+                .sum::<usize>(),
+            140
+        );
+        assert_eq!(
+            (0..regions.len())
+                .map(|region| regions.area(region) * regions.sides(region))
+                .sum::<usize>(),
+            80
+        );
+    }
+
+    #[test]
+    fn large_region_does_not_overflow() {
+        let grid = Grid::from_fn(500, 500, |_| b'.');
+        let regions = grid.connected_components(|a, b| a == b);
+
+        assert_eq!(regions.len(), 1);
+        assert_eq!(regions.area(0), 250_000);
+        assert_eq!(regions.sides(0), 4);
+    }
+
+    #[test]
+    fn flood_fill() {
+        let grid = Grid::<u8>::parse("..#\n.##\n#..").unwrap();
+        let filled = grid.flood_fill(&Coordinate::new(0, 0), |_, to| *to == b'.');
+
+        assert_eq!(filled.len(), 3);
+    }
+}
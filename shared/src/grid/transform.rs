@@ -0,0 +1,217 @@
+use crate::{Coordinate, Grid};
+
+/// A borrowed rectangular window into a [`Grid`]. Positions are relative to
+/// the window's top-left corner.
+#[derive(Debug)]
+pub struct SubGrid<'a, TCell> {
+    grid: &'a Grid<TCell>,
+    origin: Coordinate,
+    row_count: usize,
+    col_count: usize,
+}
+
+impl<'a, TCell> SubGrid<'a, TCell> {
+    pub fn row_count(&self) -> usize {
+        self.row_count
+    }
+
+    pub fn col_count(&self) -> usize {
+        self.col_count
+    }
+
+    pub fn get(&self, position: &Coordinate) -> &'a TCell {
+        assert!(
+            position.row() < self.row_count && position.col() < self.col_count,
+            "Index out of bounds"
+        );
+
+        self.grid.get(&Coordinate::new(
+            self.origin.row() + position.row(),
+            self.origin.col() + position.col(),
+        ))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &'a [TCell]> + '_ {
+        (0..self.row_count).map(|row| {
+            let start = self
+                .grid
+                .index_of(&Coordinate::new(self.origin.row() + row, self.origin.col()));
+
+            &self.grid.data[start..start + self.col_count]
+        })
+    }
+}
+
+impl<TCell> Clone for SubGrid<'_, TCell> {
+    fn clone(&self) -> Self {
+        Self {
+            grid: self.grid,
+            origin: self.origin.clone(),
+            row_count: self.row_count,
+            col_count: self.col_count,
+        }
+    }
+}
+
+impl<TCell: Clone> SubGrid<'_, TCell> {
+    pub fn to_grid(&self) -> Grid<TCell> {
+        Grid::from_fn(self.row_count, self.col_count, |position| {
+            self.get(&position).clone()
+        })
+    }
+}
+
+impl<TCell> Grid<TCell> {
+    /// A view of the `row_count` x `col_count` window starting at `top_left`
+    pub fn subgrid(
+        &self,
+        top_left: &Coordinate,
+        row_count: usize,
+        col_count: usize,
+    ) -> SubGrid<'_, TCell> {
+        assert!(
+            top_left.row() + row_count <= self.row_count
+                && top_left.col() + col_count <= self.col_count,
+            "Subgrid out of bounds"
+        );
+
+        SubGrid {
+            grid: self,
+            origin: top_left.clone(),
+            row_count,
+            col_count,
+        }
+    }
+}
+
+impl<TCell: Clone> Grid<TCell> {
+    /// Swaps rows and columns
+    pub fn transpose(&self) -> Grid<TCell> {
+        Grid::from_fn(self.col_count, self.row_count, |position| {
+            self.get(&Coordinate::new(position.col(), position.row()))
+                .clone()
+        })
+    }
+
+    /// Rotates 90° clockwise
+    pub fn rotate_90(&self) -> Grid<TCell> {
+        Grid::from_fn(self.col_count, self.row_count, |position| {
+            self.get(&Coordinate::new(
+                self.row_count - 1 - position.col(),
+                position.row(),
+            ))
+            .clone()
+        })
+    }
+
+    pub fn rotate_180(&self) -> Grid<TCell> {
+        let mut data = self.data.clone();
+        data.reverse();
+
+        Grid {
+            data,
+            row_count: self.row_count,
+            col_count: self.col_count,
+        }
+    }
+
+    /// Rotates 270° clockwise (90° counter-clockwise)
+    pub fn rotate_270(&self) -> Grid<TCell> {
+        Grid::from_fn(self.col_count, self.row_count, |position| {
+            self.get(&Coordinate::new(
+                position.col(),
+                self.col_count - 1 - position.row(),
+            ))
+            .clone()
+        })
+    }
+
+    /// Mirrors left to right
+    pub fn flip_horizontal(&self) -> Grid<TCell> {
+        Grid::from_fn(self.row_count, self.col_count, |position| {
+            self.get(&Coordinate::new(
+                position.row(),
+                self.col_count - 1 - position.col(),
+            ))
+            .clone()
+        })
+    }
+
+    /// Mirrors top to bottom
+    pub fn flip_vertical(&self) -> Grid<TCell> {
+        Grid::from_fn(self.row_count, self.col_count, |position| {
+            self.get(&Coordinate::new(
+                self.row_count - 1 - position.row(),
+                position.col(),
+            ))
+            .clone()
+        })
+    }
+
+    /// Tiles the grid `down` times vertically and `across` times horizontally
+    pub fn repeat(&self, down: usize, across: usize) -> Grid<TCell> {
+        Grid::from_fn(self.row_count * down, self.col_count * across, |position| {
+            self.get(&Coordinate::new(
+                position.row() % self.row_count,
+                position.col() % self.col_count,
+            ))
+            .clone()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::GridView;
+
+    use super::*;
+
+    fn render(grid: &Grid<u8>) -> String {
+        grid.render(|&cell| cell as char)
+    }
+
+    #[test]
+    fn transpose() {
+        let grid = Grid::<u8>::parse("abc\ndef").unwrap();
+
+        assert_eq!(render(&grid.transpose()), "ad\nbe\ncf\n");
+    }
+
+    #[test]
+    fn rotate() {
+        let grid = Grid::<u8>::parse("abc\ndef").unwrap();
+
+        assert_eq!(render(&grid.rotate_90()), "da\neb\nfc\n");
+        assert_eq!(render(&grid.rotate_180()), "fed\ncba\n");
+        assert_eq!(render(&grid.rotate_270()), "cf\nbe\nad\n");
+        assert_eq!(render(&grid.rotate_90().rotate_270()), "abc\ndef\n");
+    }
+
+    #[test]
+    fn flip() {
+        let grid = Grid::<u8>::parse("abc\ndef").unwrap();
+
+        assert_eq!(render(&grid.flip_horizontal()), "cba\nfed\n");
+        assert_eq!(render(&grid.flip_vertical()), "def\nabc\n");
+    }
+
+    #[test]
+    fn subgrid() {
+        let grid = Grid::<u8>::parse("abc\ndef\nghi").unwrap();
+        let view = grid.subgrid(&Coordinate::new(1, 1), 2, 2);
+
+        assert_eq!(*view.get(&Coordinate::new(1, 0)), b'h');
+        assert_eq!(view.rows().collect::<Vec<_>>(), vec![b"ef", b"hi"]);
+        assert_eq!(render(&view.to_grid()), "ef\nhi\n");
+    }
+
+    #[test]
+    fn repeat() {
+        let grid = Grid::<u8>::parse("ab\ncd").unwrap();
+
+        assert_eq!(
+            render(&grid.repeat(2, 3)),
+            "ababab\ncdcdcd\nababab\ncdcdcd\n"
+        );
+    }
+}
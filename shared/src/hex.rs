@@ -0,0 +1,219 @@
+use std::ops::{Add, Mul, Sub};
+
+use crate::Point3;
+
+/// A hexagon in axial coordinates on a pointy-topped grid, where `q` increases
+/// to the east and `r` increases to the south-east. The implied third cube
+/// coordinate is [`Hex::s`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Hex {
+    pub q: i64,
+    pub r: i64,
+}
+
+/// The six neighbouring directions of a pointy-topped [`Hex`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HexDirection {
+    East,
+    NorthEast,
+    NorthWest,
+    West,
+    SouthWest,
+    SouthEast,
+}
+
+impl HexDirection {
+    /// Anticlockwise from east
+    pub const ALL: [HexDirection; 6] = [
+        HexDirection::East,
+        HexDirection::NorthEast,
+        HexDirection::NorthWest,
+        HexDirection::West,
+        HexDirection::SouthWest,
+        HexDirection::SouthEast,
+    ];
+
+    pub fn opposite(self) -> HexDirection {
+        match self {
+            HexDirection::East => HexDirection::West,
+            HexDirection::NorthEast => HexDirection::SouthWest,
+            HexDirection::NorthWest => HexDirection::SouthEast,
+            HexDirection::West => HexDirection::East,
+            HexDirection::SouthWest => HexDirection::NorthEast,
+            HexDirection::SouthEast => HexDirection::NorthWest,
+        }
+    }
+
+    /// Offset of one step in this direction
+    pub fn vector(self) -> Hex {
+        match self {
+            HexDirection::East => Hex::new(1, 0),
+            HexDirection::NorthEast => Hex::new(1, -1),
+            HexDirection::NorthWest => Hex::new(0, -1),
+            HexDirection::West => Hex::new(-1, 0),
+            HexDirection::SouthWest => Hex::new(-1, 1),
+            HexDirection::SouthEast => Hex::new(0, 1),
+        }
+    }
+
+    /// Parses the `e`, `ne`, `nw`, `w`, `sw` and `se` abbreviations
+    pub fn parse(input: &str) -> Option<HexDirection> {
+        match input {
+            "e" => Some(HexDirection::East),
+            "ne" => Some(HexDirection::NorthEast),
+            "nw" => Some(HexDirection::NorthWest),
+            "w" => Some(HexDirection::West),
+            "sw" => Some(HexDirection::SouthWest),
+            "se" => Some(HexDirection::SouthEast),
+            _ => None,
+        }
+    }
+}
+
+impl Hex {
+    pub const ORIGIN: Hex = Hex::new(0, 0);
+
+    pub const fn new(q: i64, r: i64) -> Self {
+        Self { q, r }
+    }
+
+    /// The hex with cube coordinates `(q, r, s)`, or `None` unless they sum to zero
+    pub fn from_cube(q: i64, r: i64, s: i64) -> Option<Hex> {
+        (q + r + s == 0).then_some(Hex::new(q, r))
+    }
+
+    pub fn s(&self) -> i64 {
+        -self.q - self.r
+    }
+
+    pub fn step(self, direction: HexDirection) -> Hex {
+        self + direction.vector()
+    }
+
+    /// Number of steps between two hexes
+    pub fn distance(&self, other: &Hex) -> u64 {
+        Point3::from(*self).manhattan_distance(&Point3::from(*other)) / 2
+    }
+
+    /// The six adjacent hexes, in [`HexDirection::ALL`] order
+    pub fn neighbours(self) -> impl Iterator<Item = Hex> {
+        HexDirection::ALL
+            .into_iter()
+            .map(move |direction| self.step(direction))
+    }
+
+    /// Every hex exactly `radius` steps away
+    pub fn ring(self, radius: u64) -> impl Iterator<Item = Hex> {
+        let radius = radius as i64;
+        let start = self + HexDirection::SouthWest.vector() * radius;
+
+        // Walk each of the six sides, turning anticlockwise from east
+        let steps = HexDirection::ALL
+            .into_iter()
+            .flat_map(move |direction| std::iter::repeat_n(direction, radius as usize));
+
+        std::iter::once(start)
+            .chain(steps.scan(start, |hex, direction| {
+                *hex = hex.step(direction);
+                Some(*hex)
+            }))
+            .take((6 * radius).max(1) as usize)
+    }
+
+    /// Every hex at most `radius` steps away, including this one
+    pub fn within(self, radius: u64) -> impl Iterator<Item = Hex> {
+        (0..=radius).flat_map(move |distance| self.ring(distance))
+    }
+}
+
+impl Add for Hex {
+    type Output = Hex;
+
+    fn add(self, rhs: Hex) -> Hex {
+        Hex::new(self.q + rhs.q, self.r + rhs.r)
+    }
+}
+
+impl Sub for Hex {
+    type Output = Hex;
+
+    fn sub(self, rhs: Hex) -> Hex {
+        Hex::new(self.q - rhs.q, self.r - rhs.r)
+    }
+}
+
+impl Mul<i64> for Hex {
+    type Output = Hex;
+
+    fn mul(self, rhs: i64) -> Hex {
+        Hex::new(self.q * rhs, self.r * rhs)
+    }
+}
+
+/// Cube coordinates `(q, r, s)`
+impl From<Hex> for Point3 {
+    fn from(hex: Hex) -> Point3 {
+        Point3::new(hex.q, hex.r, hex.s())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bfs;
+
+    use super::*;
+
+    #[test]
+    fn directions() {
+        for direction in HexDirection::ALL {
+            assert_eq!(Hex::ORIGIN.step(direction).distance(&Hex::ORIGIN), 1);
+            assert_eq!(
+                Hex::ORIGIN.step(direction).step(direction.opposite()),
+                Hex::ORIGIN
+            );
+        }
+    }
+
+    #[test]
+    fn cube_coordinates() {
+        let hex = Hex::new(2, -3);
+
+        assert_eq!(hex.s(), 1);
+        assert_eq!(Hex::from_cube(2, -3, 1), Some(hex));
+        assert_eq!(Hex::from_cube(2, -3, 0), None);
+        assert_eq!(Point3::from(hex), Point3::new(2, -3, 1));
+    }
+
+    #[test]
+    fn rings() {
+        assert_eq!(Hex::ORIGIN.ring(0).collect::<Vec<_>>(), vec![Hex::ORIGIN]);
+
+        for radius in 1..5 {
+            let ring = Hex::ORIGIN.ring(radius).collect::<Vec<_>>();
+
+            assert_eq!(ring.len() as u64, 6 * radius);
+            assert!(ring.iter().all(|hex| hex.distance(&Hex::ORIGIN) == radius));
+        }
+
+        assert_eq!(Hex::ORIGIN.within(2).count(), 19);
+    }
+
+    #[test]
+    fn bfs_matches_distance() {
+        let start = Hex::new(1, 1);
+        let search = bfs(
+            [start],
+            |hex: &Hex| {
+                hex.neighbours()
+                    .filter(|neighbour| neighbour.distance(&start) <= 4)
+                    .collect::<Vec<_>>()
+            },
+            |_| false,
+        );
+
+        assert_eq!(search.distances().count(), start.within(4).count());
+        assert!(search
+            .distances()
+            .all(|(hex, distance)| hex.distance(&start) == distance as u64));
+    }
+}
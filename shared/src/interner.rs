@@ -0,0 +1,77 @@
+use std::collections::HashMap;
+
+/// Maps names to dense IDs, handed out from 0 in first-seen order, and back.
+#[derive(Debug, Clone, Default)]
+pub struct Interner {
+    ids: HashMap<String, usize>,
+    names: Vec<String>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The ID for `name`, only allocating the first time it's seen
+    pub fn intern(&mut self, name: &str) -> usize {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+
+        let id = self.names.len();
+        self.ids.insert(name.to_string(), id);
+        self.names.push(name.to_string());
+
+        id
+    }
+
+    /// The ID for `name`, if it has been interned
+    pub fn get(&self, name: &str) -> Option<usize> {
+        self.ids.get(name).copied()
+    }
+
+    /// # Panics
+    ///
+    /// If `id` wasn't handed out by this interner
+    pub fn name(&self, id: usize) -> &str {
+        &self.names[id]
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Every ID alongside its name, in ID order
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &str)> {
+        self.names
+            .iter()
+            .enumerate()
+            .map(|(id, name)| (id, name.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let mut interner = Interner::new();
+
+        assert_eq!(interner.intern("x00"), 0);
+        assert_eq!(interner.intern("y00"), 1);
+        assert_eq!(interner.intern("x00"), 0);
+        assert_eq!(interner.len(), 2);
+        assert_eq!(interner.get("y00"), Some(1));
+        assert_eq!(interner.get("z00"), None);
+        assert_eq!(interner.name(1), "y00");
+        assert_eq!(
+            interner.iter().collect::<Vec<_>>(),
+            vec![(0, "x00"), (1, "y00")]
+        );
+    }
+}
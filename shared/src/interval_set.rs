@@ -0,0 +1,251 @@
+use std::ops::RangeInclusive;
+
+/// A set of integers stored as inclusive intervals, kept sorted with no two
+/// intervals overlapping or touching.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntervalSet {
+    intervals: Vec<(i64, i64)>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds every value in `range`, merging with any intervals it overlaps or touches
+    pub fn insert(&mut self, range: RangeInclusive<i64>) {
+        let (mut start, mut end) = range.into_inner();
+
+        if start > end {
+            return;
+        }
+
+        // Intervals from `first` up to `last` overlap or touch the new one
+        let first = self
+            .intervals
+            .partition_point(|&(_, other_end)| other_end < start.saturating_sub(1));
+        let last = self
+            .intervals
+            .partition_point(|&(other_start, _)| other_start <= end.saturating_add(1));
+
+        if first < last {
+            start = start.min(self.intervals[first].0);
+            end = end.max(self.intervals[last - 1].1);
+        }
+
+        self.intervals.splice(first..last, [(start, end)]);
+    }
+
+    /// Removes every value in `range`
+    pub fn remove(&mut self, range: RangeInclusive<i64>) {
+        *self = self.difference(&IntervalSet::from(range));
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        let index = self.intervals.partition_point(|&(_, end)| end < value);
+
+        self.intervals
+            .get(index)
+            .is_some_and(|&(start, _)| start <= value)
+    }
+
+    /// Whether every value in `range` is in the set
+    pub fn contains_range(&self, range: &RangeInclusive<i64>) -> bool {
+        let index = self
+            .intervals
+            .partition_point(|&(_, end)| end < *range.start());
+
+        range.is_empty()
+            || self
+                .intervals
+                .get(index)
+                .is_some_and(|&(start, end)| start <= *range.start() && *range.end() <= end)
+    }
+
+    pub fn is_subset(&self, other: &IntervalSet) -> bool {
+        self.iter().all(|range| other.contains_range(&range))
+    }
+
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        let mut result = self.clone();
+
+        for range in other.iter() {
+            result.insert(range);
+        }
+
+        result
+    }
+
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let mut intervals = vec![];
+        let (mut i, mut j) = (0, 0);
+
+        while let (Some(&(a_start, a_end)), Some(&(b_start, b_end))) =
+            (self.intervals.get(i), other.intervals.get(j))
+        {
+            let (start, end) = (a_start.max(b_start), a_end.min(b_end));
+
+            if start <= end {
+                intervals.push((start, end));
+            }
+
+            // Drop whichever interval finishes first
+            if a_end < b_end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        IntervalSet { intervals }
+    }
+
+    /// Values in this set but not in `other`
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let mut intervals = vec![];
+        let mut j = 0;
+
+        for &(start, end) in &self.intervals {
+            // The part of this interval not yet handled, if any
+            let mut remaining = Some(start);
+
+            // Skip intervals of `other` that finish before this one starts
+            while other
+                .intervals
+                .get(j)
+                .is_some_and(|&(_, b_end)| b_end < start)
+            {
+                j += 1;
+            }
+
+            let mut k = j;
+            while let (Some(start), Some(&(b_start, b_end))) = (remaining, other.intervals.get(k)) {
+                if b_start > end {
+                    break;
+                }
+
+                if b_start > start {
+                    intervals.push((start, b_start - 1));
+                }
+
+                // Stepping past `end` could overflow at i64::MAX, so stop instead
+                remaining = (b_end < end).then(|| b_end + 1);
+                k += 1;
+            }
+
+            if let Some(start) = remaining {
+                intervals.push((start, end));
+            }
+        }
+
+        IntervalSet { intervals }
+    }
+
+    /// Number of values in the set, saturating at `u64::MAX` since the whole
+    /// `i64` range holds one value more than that
+    pub fn len(&self) -> u64 {
+        self.intervals.iter().fold(0, |total: u64, &(start, end)| {
+            total.saturating_add(start.abs_diff(end).saturating_add(1))
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// The normalized intervals, in order
+    pub fn iter(&self) -> impl Iterator<Item = RangeInclusive<i64>> + '_ {
+        self.intervals.iter().map(|&(start, end)| start..=end)
+    }
+
+    /// The missing values between the first and last intervals
+    pub fn gaps(&self) -> impl Iterator<Item = RangeInclusive<i64>> + '_ {
+        self.intervals
+            .windows(2)
+            .map(|pair| (pair[0].1 + 1)..=(pair[1].0 - 1))
+    }
+}
+
+impl From<RangeInclusive<i64>> for IntervalSet {
+    fn from(range: RangeInclusive<i64>) -> Self {
+        let mut result = IntervalSet::new();
+        result.insert(range);
+
+        result
+    }
+}
+
+impl FromIterator<RangeInclusive<i64>> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<i64>>>(ranges: I) -> Self {
+        let mut result = IntervalSet::new();
+
+        for range in ranges {
+            result.insert(range);
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(ranges: &[RangeInclusive<i64>]) -> IntervalSet {
+        ranges.iter().cloned().collect()
+    }
+
+    #[test]
+    fn insert_merges() {
+        let intervals = set(&[3..=5, 10..=14, 16..=20, 12..=18, 6..=6]);
+
+        assert_eq!(intervals.iter().collect::<Vec<_>>(), vec![3..=6, 10..=20]);
+        assert_eq!(intervals.len(), 15);
+        assert_eq!(intervals.gaps().collect::<Vec<_>>(), vec![7..=9]);
+    }
+
+    #[test]
+    fn membership() {
+        let intervals = set(&[3..=5, 10..=14]);
+
+        assert!(intervals.contains(3));
+        assert!(intervals.contains(14));
+        assert!(!intervals.contains(7));
+        assert!(!intervals.contains(15));
+        assert!(intervals.contains_range(&(11..=13)));
+        assert!(!intervals.contains_range(&(4..=10)));
+        assert!(set(&[4..=5]).is_subset(&intervals));
+        assert!(!set(&[4..=6]).is_subset(&intervals));
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = set(&[0..=10, 20..=30]);
+        let b = set(&[5..=25, 28..=28]);
+
+        assert_eq!(a.union(&b), set(&[0..=30]));
+        assert_eq!(a.intersection(&b), set(&[5..=10, 20..=25, 28..=28]));
+        assert_eq!(a.difference(&b), set(&[0..=4, 26..=27, 29..=30]));
+        assert_eq!(b.difference(&a), set(&[11..=19]));
+
+        let mut c = a.clone();
+        c.remove(2..=22);
+        assert_eq!(c, set(&[0..=1, 23..=30]));
+    }
+
+    #[test]
+    fn extreme_bounds() {
+        let everything = set(&[i64::MIN..=i64::MAX]);
+
+        assert_eq!(everything.len(), u64::MAX);
+        assert_eq!(set(&[i64::MIN..=-1]).len(), 1 << 63);
+        assert_eq!(
+            everything.difference(&set(&[0..=i64::MAX])),
+            set(&[i64::MIN..=-1])
+        );
+        assert_eq!(
+            set(&[0..=i64::MAX]).difference(&set(&[5..=i64::MAX])),
+            set(&[0..=4])
+        );
+    }
+}
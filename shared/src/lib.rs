@@ -1,8 +1,64 @@
+mod bit_grid;
 mod coordinate;
+pub mod cycle;
+mod direction;
+mod disjoint_set;
+mod dot;
+mod flood_fill;
+mod graph;
 mod grid;
+mod hex;
+mod interner;
+mod interval_set;
+pub mod math;
+mod memo;
+mod path;
+mod point;
+mod point3;
+mod rng;
 mod solution;
+mod sparse_grid;
+mod torus;
+mod trie;
+mod verbose;
+pub mod vm;
 
+pub use bit_grid::{BitGrid, NeighbourCounts};
 pub use coordinate::Coordinate;
+pub use direction::Direction;
+pub use disjoint_set::DisjointSet;
+pub use dot::{Dot, DotCluster, DotEdge, DotNode};
+pub use flood_fill::flood_fill;
+pub use graph::Graph;
+pub use grid::DistanceField;
+pub use grid::FromByte;
 pub use grid::Grid;
 pub use grid::GridCursor;
+pub use grid::GridError;
+pub use grid::GridView;
+pub use grid::Markers;
+pub use grid::Regions;
+pub use grid::Shortcut;
+pub use grid::SubGrid;
+pub use hex::Hex;
+pub use hex::HexDirection;
+pub use interner::Interner;
+pub use interval_set::IntervalSet;
+pub use memo::Memo;
+pub use path::astar;
+pub use path::bfs;
+pub use path::dijkstra;
+pub use path::ShortestPaths;
+pub use path::ZeroCostCycle;
+pub use point::Point;
+pub use point::Vector;
+pub use point3::BoundingBox3;
+pub use point3::Point3;
+pub use point3::Vector3;
+pub use rng::XorShift;
 pub use solution::Solution;
+pub use sparse_grid::SparseGrid;
+pub use torus::Torus;
+pub use trie::Trie;
+pub use verbose::is_verbose;
+pub use verbose::set_verbose;
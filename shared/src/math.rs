@@ -0,0 +1,410 @@
+//! Integer number theory helpers.
+
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+
+    a
+}
+
+pub fn lcm(a: u64, b: u64) -> u64 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+
+    a / gcd(a, b) * b
+}
+
+/// `(g, x, y)` where `g = gcd(a, b) >= 0` and `a * x + b * y = g`
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (g, x, y) = extended_gcd_wide(a as i128, b as i128);
+
+    (g as i64, x as i64, y as i64)
+}
+
+fn extended_gcd_wide(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let quotient = old_r / r;
+
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// `x` in `0..modulus` with `a * x ≡ 1 (mod modulus)`, if `a` and `modulus` are coprime
+pub fn mod_inverse(a: i64, modulus: i64) -> Option<i64> {
+    let (g, x, _) = extended_gcd(a.rem_euclid(modulus), modulus);
+
+    (g == 1).then(|| x.rem_euclid(modulus))
+}
+
+/// `base ^ exponent mod modulus`, by repeated squaring
+pub fn mod_pow(base: u64, mut exponent: u64, modulus: u64) -> u64 {
+    let modulus = modulus as u128;
+    let mut base = base as u128 % modulus;
+    let mut result = 1 % modulus;
+
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result * base % modulus;
+        }
+
+        base = base * base % modulus;
+        exponent >>= 1;
+    }
+
+    result as u64
+}
+
+/// Combines congruences `x ≡ residue (mod modulus)` into a single
+/// `(residue, modulus)`, where the modulus is the lcm of the inputs. The moduli
+/// don't need to be coprime; returns `None` if the congruences conflict.
+pub fn chinese_remainder(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    let mut result: (i128, i128) = (0, 1);
+
+    for &(residue, modulus) in congruences {
+        let (residue, modulus) = (residue as i128, modulus as i128);
+        let (current, current_modulus) = result;
+        let (g, p, _) = extended_gcd_wide(current_modulus, modulus);
+        let difference = residue - current;
+
+        if difference % g != 0 {
+            return None;
+        }
+
+        // current + current_modulus * k ≡ residue (mod modulus)
+        let step = modulus / g;
+        let k = (difference / g % step * p % step).rem_euclid(step);
+        let combined_modulus = current_modulus * step;
+
+        result = (
+            (current + current_modulus * k).rem_euclid(combined_modulus),
+            combined_modulus,
+        );
+    }
+
+    Some((result.0 as i64, result.1 as i64))
+}
+
+/// Non-negative integer solutions to a 2×2 linear system.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinearSolutions {
+    None,
+    One(i64, i64),
+    /// More than one solution, all on a line
+    Many(SolutionFamily),
+    /// Every pair of non-negative integers, when both equations are `0 = 0`
+    All,
+}
+
+/// The solutions `first + k * step` for `k` in `0..count`, where `count` is
+/// `None` when there are infinitely many.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SolutionFamily {
+    pub first: (i64, i64),
+    pub step: (i64, i64),
+    pub count: Option<u64>,
+}
+
+impl SolutionFamily {
+    /// The `k`th solution, if the family has that many
+    pub fn nth(&self, k: u64) -> Option<(i64, i64)> {
+        if self.count.is_some_and(|count| k >= count) {
+            return None;
+        }
+
+        let k = k as i64;
+
+        Some((
+            self.first.0 + k * self.step.0,
+            self.first.1 + k * self.step.1,
+        ))
+    }
+
+    /// The solution minimising `cost.0 * x + cost.1 * y` among those with
+    /// both values at most `max`, or `None` if there is no such solution or
+    /// the cost falls forever along an infinite family
+    pub fn cheapest(&self, cost: (i64, i64), max: Option<i64>) -> Option<(i64, i64)> {
+        // The k values to consider, inclusive, with no upper end for an
+        // infinite family
+        let mut low = 0;
+        let mut high = self.count.map(|count| count as i64 - 1);
+
+        if let Some(max) = max {
+            for (first, step) in [(self.first.0, self.step.0), (self.first.1, self.step.1)] {
+                if step > 0 {
+                    let bound = (max - first).div_euclid(step);
+
+                    high = Some(high.map_or(bound, |high| high.min(bound)));
+                } else if step < 0 {
+                    // Rounded up, as the value only drops to `max` from above
+                    low = low.max(-(max - first).div_euclid(-step));
+                } else if first > max {
+                    return None;
+                }
+            }
+        }
+
+        if high.is_some_and(|high| high < low) {
+            return None;
+        }
+
+        // The cost is linear in k, so the minimum is at one end of the range
+        let change = cost.0 as i128 * self.step.0 as i128 + cost.1 as i128 * self.step.1 as i128;
+        let k = if change >= 0 { low } else { high? };
+
+        self.nth(k as u64)
+    }
+}
+
+/// Solves `a x + b y = e`, `c x + d y = f` over the non-negative integers,
+/// given as `[[a, b], [c, d]]` and `[e, f]`. Exact, so safe for large values.
+pub fn solve_2x2(matrix: [[i64; 2]; 2], constants: [i64; 2]) -> LinearSolutions {
+    let [[a, b], [c, d]] = matrix.map(|row| row.map(|value| value as i128));
+    let [e, f] = constants.map(|value| value as i128);
+    let determinant = a * d - b * c;
+
+    if determinant != 0 {
+        // Cramer's rule
+        let x_numerator = e * d - b * f;
+        let y_numerator = a * f - e * c;
+
+        if x_numerator % determinant != 0 || y_numerator % determinant != 0 {
+            return LinearSolutions::None;
+        }
+
+        let (x, y) = (x_numerator / determinant, y_numerator / determinant);
+
+        return if x >= 0 && y >= 0 {
+            LinearSolutions::One(x as i64, y as i64)
+        } else {
+            LinearSolutions::None
+        };
+    }
+
+    // The rows are parallel, so the system is one equation if they agree
+    if a * f != c * e || b * f != d * e {
+        return LinearSolutions::None;
+    }
+
+    let (a, b, e) = if a != 0 || b != 0 {
+        (a, b, e)
+    } else {
+        (c, d, f)
+    };
+
+    solve_single(a, b, e)
+}
+
+/// Non-negative integer solutions to `a x + b y = c`
+fn solve_single(a: i128, b: i128, c: i128) -> LinearSolutions {
+    if a == 0 && b == 0 {
+        return if c == 0 {
+            LinearSolutions::All
+        } else {
+            LinearSolutions::None
+        };
+    }
+
+    if a == 0 || b == 0 {
+        // One variable is fixed and the other is free
+        let coefficient = a + b;
+
+        if c % coefficient != 0 || c / coefficient < 0 {
+            return LinearSolutions::None;
+        }
+
+        let fixed = (c / coefficient) as i64;
+        let (first, step) = if a == 0 {
+            ((0, fixed), (1, 0))
+        } else {
+            ((fixed, 0), (0, 1))
+        };
+
+        return LinearSolutions::Many(SolutionFamily {
+            first,
+            step,
+            count: None,
+        });
+    }
+
+    let (g, p, q) = extended_gcd_wide(a, b);
+
+    if c % g != 0 {
+        return LinearSolutions::None;
+    }
+
+    // Every solution is (x0 + t b/g, y0 - t a/g); find the t keeping both non-negative
+    let (x0, y0) = (p * (c / g), q * (c / g));
+    let (x_step, y_step) = (b / g, -a / g);
+    let mut lower = None;
+    let mut upper = None;
+
+    for (start, step) in [(x0, x_step), (y0, y_step)] {
+        // start + step * t >= 0
+        if step > 0 {
+            let bound = div_ceil(-start, step);
+            lower = Some(lower.map_or(bound, |lower: i128| lower.max(bound)));
+        } else {
+            let bound = div_floor(-start, step);
+            upper = Some(upper.map_or(bound, |upper: i128| upper.min(bound)));
+        }
+    }
+
+    let solution = |t: i128| ((x0 + x_step * t) as i64, (y0 + y_step * t) as i64);
+
+    // b/g is never 0 here, so x_step's sign bounds t from one side at least.
+    // The family starts from that side and steps into the valid range.
+    match (lower, upper) {
+        (Some(lower), Some(upper)) if lower > upper => LinearSolutions::None,
+        (Some(lower), Some(upper)) if lower == upper => {
+            let (x, y) = solution(lower);
+            LinearSolutions::One(x, y)
+        }
+        (Some(lower), upper) => LinearSolutions::Many(SolutionFamily {
+            first: solution(lower),
+            step: (x_step as i64, y_step as i64),
+            count: upper.map(|upper| (upper - lower + 1) as u64),
+        }),
+        (None, Some(upper)) => LinearSolutions::Many(SolutionFamily {
+            first: solution(upper),
+            step: (-x_step as i64, -y_step as i64),
+            count: None,
+        }),
+        (None, None) => unreachable!("a and b are non-zero, so t is bounded on some side"),
+    }
+}
+
+fn div_floor(numerator: i128, denominator: i128) -> i128 {
+    let quotient = numerator / denominator;
+
+    if numerator % denominator != 0 && (numerator < 0) != (denominator < 0) {
+        quotient - 1
+    } else {
+        quotient
+    }
+}
+
+fn div_ceil(numerator: i128, denominator: i128) -> i128 {
+    -div_floor(-numerator, denominator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcd_and_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(0, 5), 5);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(
+            [2, 3, 5, 7, 11, 13, 17, 19].into_iter().fold(1, lcm),
+            9699690
+        );
+    }
+
+    #[test]
+    fn extended_euclid_and_inverse() {
+        let (g, x, y) = extended_gcd(240, -46);
+
+        assert_eq!(g, 2);
+        assert_eq!(240 * x - 46 * y, 2);
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(6, 9), None);
+        assert_eq!(mod_pow(4, 13, 497), 445);
+        assert_eq!(mod_pow(u64::MAX, u64::MAX, 1_000_000_007), 254368884);
+    }
+
+    #[test]
+    fn chinese_remainder_theorem() {
+        assert_eq!(
+            chinese_remainder(&[(2, 3), (3, 5), (2, 7)]),
+            Some((23, 105))
+        );
+        // Non-coprime moduli
+        assert_eq!(chinese_remainder(&[(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(chinese_remainder(&[(1, 4), (2, 6)]), None);
+        assert_eq!(chinese_remainder(&[]), Some((0, 1)));
+    }
+
+    #[test]
+    fn linear_systems() {
+        // 2024 day 13's first example machine
+        assert_eq!(
+            solve_2x2([[94, 22], [34, 67]], [8400, 5400]),
+            LinearSolutions::One(80, 40)
+        );
+        // Unique solution, but not integral
+        assert_eq!(
+            solve_2x2([[26, 67], [66, 21]], [12748, 12176]),
+            LinearSolutions::None
+        );
+        // Unique solution, but negative
+        assert_eq!(solve_2x2([[1, 1], [1, -1]], [0, 2]), LinearSolutions::None);
+        // x + y = 3 twice over: (0, 3), (1, 2), (2, 1), (3, 0)
+        let LinearSolutions::Many(family) = solve_2x2([[1, 1], [2, 2]], [3, 6]) else {
+            panic!("expected a family of solutions");
+        };
+        let mut solutions = (0..4).map(|k| family.nth(k).unwrap()).collect::<Vec<_>>();
+        solutions.sort();
+        assert_eq!(family.count, Some(4));
+        assert_eq!(solutions, vec![(0, 3), (1, 2), (2, 1), (3, 0)]);
+        assert_eq!(family.nth(4), None);
+        assert_eq!(family.cheapest((3, 1), None), Some((0, 3)));
+        assert_eq!(family.cheapest((1, 3), None), Some((3, 0)));
+        assert_eq!(family.cheapest((3, 1), Some(2)), Some((1, 2)));
+        assert_eq!(family.cheapest((1, 3), Some(2)), Some((2, 1)));
+        assert_eq!(family.cheapest((3, 1), Some(1)), None);
+        // 2x + 4y = 2: only (1, 0)
+        assert_eq!(
+            solve_2x2([[2, 4], [1, 2]], [2, 1]),
+            LinearSolutions::One(1, 0)
+        );
+        // x - y = 1: (1, 0), (2, 1), ... without end
+        assert_eq!(
+            solve_2x2([[1, -1], [2, -2]], [1, 2]),
+            LinearSolutions::Many(SolutionFamily {
+                first: (1, 0),
+                step: (1, 1),
+                count: None
+            })
+        );
+        let LinearSolutions::Many(family) = solve_2x2([[1, -1], [2, -2]], [1, 2]) else {
+            unreachable!();
+        };
+        assert_eq!(family.cheapest((-1, 0), None), None);
+        assert_eq!(family.cheapest((-1, 0), Some(5)), Some((5, 4)));
+        assert_eq!(family.cheapest((1, 1), Some(5)), Some((1, 0)));
+        // 3y = 6 with x free
+        assert_eq!(
+            solve_2x2([[0, 3], [0, 0]], [6, 0]),
+            LinearSolutions::Many(SolutionFamily {
+                first: (0, 2),
+                step: (1, 0),
+                count: None
+            })
+        );
+        assert_eq!(solve_2x2([[0, 0], [0, 0]], [0, 0]), LinearSolutions::All);
+        // Parallel but inconsistent
+        assert_eq!(solve_2x2([[1, 1], [1, 1]], [1, 2]), LinearSolutions::None);
+        // Large right-hand sides from part 2
+        assert_eq!(
+            solve_2x2([[26, 67], [66, 21]], [10000000012748, 10000000012176]),
+            LinearSolutions::One(118679050709, 103199174542)
+        );
+    }
+}
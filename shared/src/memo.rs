@@ -0,0 +1,107 @@
+use std::{borrow::Borrow, collections::HashMap, hash::Hash};
+
+use crate::is_verbose;
+
+/// A cache for memoized recursion. Lookups take a borrowed key, so the key is
+/// only cloned into the cache on a miss. In verbose mode the hit and miss
+/// counts are printed when the cache is dropped.
+#[derive(Debug)]
+pub struct Memo<K, V> {
+    name: &'static str,
+    cache: HashMap<K, V>,
+    hits: u64,
+    misses: u64,
+}
+
+impl<K: Eq + Hash, V: Clone> Memo<K, V> {
+    /// `name` identifies the cache in verbose output
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            cache: HashMap::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// The cached value for `key`, or the result of `compute`, which is given
+    /// the cache back so it can recurse
+    pub fn get_or_compute<Q>(&mut self, key: &Q, compute: impl FnOnce(&mut Self) -> V) -> V
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ToOwned<Owned = K> + ?Sized,
+    {
+        if let Some(value) = self.cache.get(key) {
+            self.hits += 1;
+
+            return value.clone();
+        }
+
+        self.misses += 1;
+        let value = compute(self);
+        self.cache.insert(key.to_owned(), value.clone());
+
+        value
+    }
+
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+}
+
+impl<K, V> Drop for Memo<K, V> {
+    fn drop(&mut self) {
+        if is_verbose() {
+            eprintln!(
+                "[memo] {}: {} hits, {} misses, {} entries",
+                self.name,
+                self.hits,
+                self.misses,
+                self.cache.len()
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fibonacci(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+        if n < 2 {
+            return n;
+        }
+
+        memo.get_or_compute(&n, |memo| fibonacci(n - 1, memo) + fibonacci(n - 2, memo))
+    }
+
+    #[test]
+    fn recursion() {
+        let mut memo = Memo::new("fibonacci");
+
+        assert_eq!(fibonacci(90, &mut memo), 2880067194370816120);
+        assert_eq!(memo.misses(), 89);
+        assert_eq!(memo.hits(), 87);
+    }
+
+    #[test]
+    fn borrowed_keys() {
+        let mut memo: Memo<String, usize> = Memo::new("lengths");
+
+        assert_eq!(memo.get_or_compute("abc", |_| 3), 3);
+        assert_eq!(memo.get_or_compute("abc", |_| unreachable!()), 3);
+        assert_eq!(memo.len(), 1);
+    }
+}
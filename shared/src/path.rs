@@ -0,0 +1,409 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet},
+    error::Error,
+    fmt::{self, Display},
+    hash::Hash,
+    ops::Add,
+};
+
+/// The outcome of a shortest-path search: settled distances, the goals reached
+/// at the optimal cost, and every predecessor that lies on an optimal route.
+///
+/// Costs must be non-negative, and any heuristic must be consistent, for the
+/// predecessor DAG to hold every optimal path.
+#[derive(Debug, Clone)]
+pub struct ShortestPaths<N, C> {
+    nodes: Vec<N>,
+    indices: HashMap<N, usize>,
+    costs: Vec<C>,
+    predecessors: Vec<Vec<usize>>,
+    /// Node indices in the order they were settled
+    settled: Vec<usize>,
+    is_settled: Vec<bool>,
+    goals: Vec<usize>,
+}
+
+/// Zero-cost moves form a loop among optimal predecessors, so there are
+/// infinitely many optimal paths
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZeroCostCycle;
+
+impl Display for ZeroCostCycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "zero-cost moves loop along an optimal path")
+    }
+}
+
+impl Error for ZeroCostCycle {}
+
+/// Breadth-first search where every move costs 1.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> ShortestPaths<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    dijkstra(
+        starts,
+        |node| successors(node).into_iter().map(|next| (next, 1)),
+        is_goal,
+    )
+}
+
+/// Dijkstra's algorithm. Stops once every goal at the optimal cost has been
+/// reached, or explores everything reachable if `is_goal` never matches.
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> ShortestPaths<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(starts, successors, |_| C::default(), is_goal)
+}
+
+/// A* search guided by `heuristic`, a lower bound on the remaining cost to a goal.
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> ShortestPaths<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut result = ShortestPaths {
+        nodes: vec![],
+        indices: HashMap::new(),
+        costs: vec![],
+        predecessors: vec![],
+        settled: vec![],
+        is_settled: vec![],
+        goals: vec![],
+    };
+    let mut queue = BinaryHeap::new();
+    let mut best = None;
+
+    for start in starts {
+        if !result.indices.contains_key(&start) {
+            let estimate = heuristic(&start);
+            let index = result.add_node(start, C::default());
+
+            queue.push(Reverse((estimate, C::default(), index)));
+        }
+    }
+
+    while let Some(Reverse((estimate, cost, index))) = queue.pop() {
+        if best.is_some_and(|best| estimate > best) {
+            break;
+        }
+
+        if result.is_settled[index] {
+            continue;
+        }
+
+        result.is_settled[index] = true;
+        result.settled.push(index);
+
+        if is_goal(&result.nodes[index]) {
+            best = Some(cost);
+            result.goals.push(index);
+            continue;
+        }
+
+        for (next, step_cost) in successors(&result.nodes[index]) {
+            let next_cost = cost + step_cost;
+
+            let next_index = match result.indices.get(&next) {
+                Some(&next_index) => {
+                    let known_cost = result.costs[next_index];
+
+                    // Another optimal way in, or no better than what's already known
+                    if next_cost == known_cost {
+                        result.predecessors[next_index].push(index);
+                    }
+                    if next_cost >= known_cost || result.is_settled[next_index] {
+                        continue;
+                    }
+
+                    result.costs[next_index] = next_cost;
+                    result.predecessors[next_index] = vec![index];
+                    next_index
+                }
+                None => {
+                    let next_index = result.add_node(next, next_cost);
+                    result.predecessors[next_index].push(index);
+                    next_index
+                }
+            };
+
+            let estimate = next_cost + heuristic(&result.nodes[next_index]);
+            queue.push(Reverse((estimate, next_cost, next_index)));
+        }
+    }
+
+    result
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> ShortestPaths<N, C> {
+    fn add_node(&mut self, node: N, cost: C) -> usize {
+        let index = self.nodes.len();
+
+        self.indices.insert(node.clone(), index);
+        self.nodes.push(node);
+        self.costs.push(cost);
+        self.predecessors.push(vec![]);
+        self.is_settled.push(false);
+
+        index
+    }
+
+    /// Cost of reaching the nearest goal, or `None` if no goal was reachable
+    pub fn cost(&self) -> Option<C> {
+        self.goals.first().map(|&goal| self.costs[goal])
+    }
+
+    /// Optimal cost of reaching `node`, if the search settled it
+    pub fn distance(&self, node: &N) -> Option<C> {
+        let &index = self.indices.get(node)?;
+
+        self.is_settled[index].then_some(self.costs[index])
+    }
+
+    /// Every settled node with its optimal cost
+    pub fn distances(&self) -> impl Iterator<Item = (&N, C)> {
+        self.settled
+            .iter()
+            .map(|&index| (&self.nodes[index], self.costs[index]))
+    }
+
+    /// Goals reached at the optimal cost
+    pub fn goals(&self) -> impl Iterator<Item = &N> {
+        self.goals.iter().map(|&goal| &self.nodes[goal])
+    }
+
+    /// Nodes immediately before `node` on some optimal path to it
+    pub fn predecessors(&self, node: &N) -> impl Iterator<Item = &N> {
+        self.indices
+            .get(node)
+            .into_iter()
+            .flat_map(|&index| &self.predecessors[index])
+            .map(|&index| &self.nodes[index])
+    }
+
+    /// One optimal path from a start to the first goal, inclusive
+    pub fn path(&self) -> Option<Vec<N>> {
+        let mut index = *self.goals.first()?;
+        let mut path = vec![self.nodes[index].clone()];
+
+        while let Some(&previous) = self.predecessors[index].first() {
+            index = previous;
+            path.push(self.nodes[index].clone());
+        }
+
+        path.reverse();
+
+        Some(path)
+    }
+
+    /// Every node lying on at least one optimal path to a goal
+    pub fn optimal_nodes(&self) -> HashSet<N> {
+        let mut visited = vec![false; self.nodes.len()];
+        let mut stack = self.goals.clone();
+
+        while let Some(index) = stack.pop() {
+            if !std::mem::replace(&mut visited[index], true) {
+                stack.extend(&self.predecessors[index]);
+            }
+        }
+
+        visited
+            .iter()
+            .zip(&self.nodes)
+            .filter(|(&visited, _)| visited)
+            .map(|(_, node)| node.clone())
+            .collect()
+    }
+
+    /// Number of distinct optimal paths to any goal. Fails if zero-cost moves
+    /// loop back along an optimal path, as there are then infinitely many.
+    pub fn count_paths(&self) -> Result<u64, ZeroCostCycle> {
+        #[derive(Clone, Copy)]
+        enum Visit {
+            New,
+            /// On the current walk, waiting for its predecessors' counts
+            Open,
+            Counted(u64),
+        }
+
+        let mut visits = vec![Visit::New; self.nodes.len()];
+        let mut stack = self.goals.clone();
+
+        // Post-order walk back through the predecessors, without recursion
+        while let Some(&index) = stack.last() {
+            match visits[index] {
+                Visit::Counted(_) => {
+                    stack.pop();
+                }
+                Visit::New => {
+                    visits[index] = Visit::Open;
+
+                    for &previous in &self.predecessors[index] {
+                        match visits[previous] {
+                            Visit::New => stack.push(previous),
+                            // Everything open is on the walk back to a goal from here
+                            Visit::Open => return Err(ZeroCostCycle),
+                            Visit::Counted(_) => {}
+                        }
+                    }
+                }
+                Visit::Open => {
+                    stack.pop();
+
+                    let count = if self.predecessors[index].is_empty() {
+                        1
+                    } else {
+                        self.predecessors[index]
+                            .iter()
+                            .map(|&previous| match visits[previous] {
+                                Visit::Counted(count) => count,
+                                _ => {
+                                    unreachable!("predecessors are counted before their successors")
+                                }
+                            })
+                            .sum()
+                    };
+
+                    visits[index] = Visit::Counted(count);
+                }
+            }
+        }
+
+        Ok(self
+            .goals
+            .iter()
+            .map(|&goal| match visits[goal] {
+                Visit::Counted(count) => count,
+                _ => 0,
+            })
+            .sum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Point;
+
+    use super::*;
+
+    /// Moves right or down on an open `size` x `size` grid
+    fn lattice(size: i64) -> impl FnMut(&Point) -> Vec<Point> {
+        move |point| {
+            [
+                Point::new(point.x + 1, point.y),
+                Point::new(point.x, point.y + 1),
+            ]
+            .into_iter()
+            .filter(|next| next.x < size && next.y < size)
+            .collect()
+        }
+    }
+
+    #[test]
+    fn bfs_counts_lattice_paths() {
+        let goal = Point::new(3, 3);
+        let result = bfs([Point::ORIGIN], lattice(4), |&point| point == goal);
+
+        assert_eq!(result.cost(), Some(6));
+        assert_eq!(result.count_paths(), Ok(20));
+        assert_eq!(result.optimal_nodes().len(), 16);
+        assert_eq!(result.path().unwrap().len(), 7);
+    }
+
+    #[test]
+    fn dijkstra_prefers_cheap_route() {
+        // 0 -> 1 -> 3 costs 2, 0 -> 2 -> 3 costs 2, 0 -> 3 costs 5
+        let edges = HashMap::from([
+            (0, vec![(1, 1), (2, 1), (3, 5)]),
+            (1, vec![(3, 1)]),
+            (2, vec![(3, 1)]),
+        ]);
+        let result = dijkstra(
+            [0],
+            |node| edges.get(node).cloned().unwrap_or_default(),
+            |&node| node == 3,
+        );
+
+        assert_eq!(result.cost(), Some(2));
+        assert_eq!(result.count_paths(), Ok(2));
+        assert_eq!(result.optimal_nodes(), HashSet::from([0, 1, 2, 3]));
+
+        let mut predecessors = result.predecessors(&3).copied().collect::<Vec<_>>();
+        predecessors.sort();
+        assert_eq!(predecessors, vec![1, 2]);
+    }
+
+    #[test]
+    fn astar_matches_dijkstra() {
+        let goal = Point::new(7, 7);
+        let successors = |point: &Point| {
+            point
+                .neighbours()
+                .filter(|next| (0..8).contains(&next.x) && (0..8).contains(&next.y))
+                .filter(|next| !(next.x == 4 && next.y < 6))
+                .map(|next| (next, 1))
+                .collect::<Vec<_>>()
+        };
+
+        let with_heuristic = astar(
+            [Point::ORIGIN],
+            successors,
+            |point| point.manhattan_distance(&goal) as usize,
+            |&point| point == goal,
+        );
+        let without = dijkstra([Point::ORIGIN], successors, |&point| point == goal);
+
+        assert_eq!(with_heuristic.cost(), without.cost());
+        assert_eq!(with_heuristic.count_paths(), without.count_paths());
+    }
+
+    #[test]
+    fn zero_cost_loop() {
+        // 1 and 2 reach each other for free, so 0 -> 1 -> 2 -> 1 -> 2 -> 3 is optimal too
+        let edges = HashMap::from([
+            (0, vec![(1, 0)]),
+            (1, vec![(2, 0)]),
+            (2, vec![(1, 0), (3, 1)]),
+        ]);
+        let result = dijkstra(
+            [0],
+            |node| edges.get(node).cloned().unwrap_or_default(),
+            |&node| node == 3,
+        );
+
+        assert_eq!(result.cost(), Some(1));
+        assert_eq!(result.path(), Some(vec![0, 1, 2, 3]));
+        assert_eq!(result.count_paths(), Err(ZeroCostCycle));
+    }
+
+    #[test]
+    fn unreachable_goal() {
+        let result = bfs([Point::ORIGIN], lattice(2), |&point| {
+            point == Point::new(5, 5)
+        });
+
+        assert_eq!(result.cost(), None);
+        assert_eq!(result.path(), None);
+        assert_eq!(result.count_paths(), Ok(0));
+        assert_eq!(result.distances().count(), 4);
+    }
+}
@@ -0,0 +1,177 @@
+use std::{
+    num::TryFromIntError,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+use crate::{Coordinate, Direction};
+
+/// A signed 2D position, with `y` increasing downwards like grid rows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+/// A displacement between two [`Point`]s, such as a velocity.
+pub type Vector = Point;
+
+impl Point {
+    pub const ORIGIN: Point = Point::new(0, 0);
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    /// The point one step away in `direction`
+    pub fn step(self, direction: Direction) -> Point {
+        self + direction.vector()
+    }
+
+    pub fn manhattan_distance(&self, other: &Point) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// Number of king moves between two points
+    pub fn chebyshev_distance(&self, other: &Point) -> u64 {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// Each component reduced to -1, 0 or 1
+    pub fn signum(self) -> Vector {
+        Point::new(self.x.signum(), self.y.signum())
+    }
+
+    /// Orthogonally adjacent points, in [`Direction::ALL`] order
+    pub fn neighbours(self) -> impl Iterator<Item = Point> {
+        Direction::ALL
+            .into_iter()
+            .map(move |direction| self.step(direction))
+    }
+
+    /// Orthogonally and diagonally adjacent points, clockwise from up
+    pub fn all_neighbours(self) -> impl Iterator<Item = Point> {
+        [
+            (0, -1),
+            (1, -1),
+            (1, 0),
+            (1, 1),
+            (0, 1),
+            (-1, 1),
+            (-1, 0),
+            (-1, -1),
+        ]
+        .into_iter()
+        .map(move |(x, y)| self + Point::new(x, y))
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Point) -> Point {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, rhs: Point) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Point {
+    type Output = Vector;
+
+    fn sub(self, rhs: Point) -> Vector {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, rhs: Point) {
+        *self = *self - rhs;
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<i64> for Point {
+    type Output = Point;
+
+    fn mul(self, rhs: i64) -> Point {
+        Point::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl From<Coordinate> for Point {
+    fn from(coordinate: Coordinate) -> Point {
+        Point::new(coordinate.x() as i64, coordinate.y() as i64)
+    }
+}
+
+impl From<&Coordinate> for Point {
+    fn from(coordinate: &Coordinate) -> Point {
+        Point::new(coordinate.x() as i64, coordinate.y() as i64)
+    }
+}
+
+/// Fails for points with a negative component
+impl TryFrom<Point> for Coordinate {
+    type Error = TryFromIntError;
+
+    fn try_from(point: Point) -> Result<Coordinate, TryFromIntError> {
+        Ok(Coordinate::new(point.y.try_into()?, point.x.try_into()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let a = Point::new(3, -2);
+        let b = Point::new(-1, 5);
+
+        assert_eq!(a + b, Point::new(2, 3));
+        assert_eq!(a - b, Point::new(4, -7));
+        assert_eq!(-a, Point::new(-3, 2));
+        assert_eq!(a * 3, Point::new(9, -6));
+        assert_eq!(a.manhattan_distance(&b), 11);
+        assert_eq!(a.chebyshev_distance(&b), 7);
+    }
+
+    #[test]
+    fn neighbours() {
+        let point = Point::new(0, 0);
+
+        assert_eq!(
+            point.neighbours().collect::<Vec<_>>(),
+            vec![
+                Point::new(0, -1),
+                Point::new(1, 0),
+                Point::new(0, 1),
+                Point::new(-1, 0)
+            ]
+        );
+        assert_eq!(point.all_neighbours().count(), 8);
+        assert!(point
+            .all_neighbours()
+            .all(|neighbour| neighbour.chebyshev_distance(&point) == 1));
+    }
+
+    #[test]
+    fn coordinate_conversion() {
+        let point = Point::from(Coordinate::new(2, 5));
+
+        assert_eq!(point, Point::new(5, 2));
+        assert_eq!(Coordinate::try_from(point), Ok(Coordinate::new(2, 5)));
+        assert!(Coordinate::try_from(Point::new(-1, 0)).is_err());
+    }
+}
@@ -0,0 +1,217 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+
+/// A signed 3D position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+/// A displacement between two [`Point3`]s.
+pub type Vector3 = Point3;
+
+impl Point3 {
+    pub const ORIGIN: Point3 = Point3::new(0, 0, 0);
+
+    pub const fn new(x: i64, y: i64, z: i64) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn manhattan_distance(&self, other: &Point3) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+
+    pub fn chebyshev_distance(&self, other: &Point3) -> u64 {
+        self.x
+            .abs_diff(other.x)
+            .max(self.y.abs_diff(other.y))
+            .max(self.z.abs_diff(other.z))
+    }
+
+    /// The six points sharing a face with this one
+    pub fn neighbours(self) -> impl Iterator<Item = Point3> {
+        [
+            (1, 0, 0),
+            (-1, 0, 0),
+            (0, 1, 0),
+            (0, -1, 0),
+            (0, 0, 1),
+            (0, 0, -1),
+        ]
+        .into_iter()
+        .map(move |(x, y, z)| self + Point3::new(x, y, z))
+    }
+
+    /// The 26 points sharing a face, edge or corner with this one
+    pub fn all_neighbours(self) -> impl Iterator<Item = Point3> {
+        (-1..=1)
+            .flat_map(|x| (-1..=1).flat_map(move |y| (-1..=1).map(move |z| (x, y, z))))
+            .filter(|&offset| offset != (0, 0, 0))
+            .map(move |(x, y, z)| self + Point3::new(x, y, z))
+    }
+}
+
+impl Add for Point3 {
+    type Output = Point3;
+
+    fn add(self, rhs: Point3) -> Point3 {
+        Point3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl AddAssign for Point3 {
+    fn add_assign(&mut self, rhs: Point3) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Point3 {
+    type Output = Vector3;
+
+    fn sub(self, rhs: Point3) -> Vector3 {
+        Point3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl Neg for Point3 {
+    type Output = Point3;
+
+    fn neg(self) -> Point3 {
+        Point3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl Mul<i64> for Point3 {
+    type Output = Point3;
+
+    fn mul(self, rhs: i64) -> Point3 {
+        Point3::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+/// An inclusive axis-aligned box of [`Point3`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BoundingBox3 {
+    pub min: Point3,
+    pub max: Point3,
+}
+
+impl BoundingBox3 {
+    /// The smallest box containing every point, or `None` if there are none
+    pub fn from_points<'a>(points: impl IntoIterator<Item = &'a Point3>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = *points.next()?;
+
+        Some(points.fold(
+            BoundingBox3 {
+                min: first,
+                max: first,
+            },
+            |bounds, point| BoundingBox3 {
+                min: Point3::new(
+                    bounds.min.x.min(point.x),
+                    bounds.min.y.min(point.y),
+                    bounds.min.z.min(point.z),
+                ),
+                max: Point3::new(
+                    bounds.max.x.max(point.x),
+                    bounds.max.y.max(point.y),
+                    bounds.max.z.max(point.z),
+                ),
+            },
+        ))
+    }
+
+    pub fn contains(&self, point: &Point3) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x)
+            && (self.min.y..=self.max.y).contains(&point.y)
+            && (self.min.z..=self.max.z).contains(&point.z)
+    }
+
+    /// The box grown by `margin` on every side
+    pub fn expand(&self, margin: i64) -> BoundingBox3 {
+        let margin = Point3::new(margin, margin, margin);
+
+        BoundingBox3 {
+            min: self.min - margin,
+            max: self.max + margin,
+        }
+    }
+
+    /// Number of points inside the box
+    pub fn volume(&self) -> u64 {
+        let size = self.max - self.min;
+
+        (size.x + 1) as u64 * (size.y + 1) as u64 * (size.z + 1) as u64
+    }
+
+    /// Every point inside the box, ordered by `x`, then `y`, then `z`
+    pub fn iter(&self) -> impl Iterator<Item = Point3> {
+        let BoundingBox3 { min, max } = *self;
+
+        (min.x..=max.x).flat_map(move |x| {
+            (min.y..=max.y).flat_map(move |y| (min.z..=max.z).map(move |z| Point3::new(x, y, z)))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::flood_fill;
+
+    use super::*;
+
+    #[test]
+    fn neighbours_and_distances() {
+        let point = Point3::new(1, 2, 3);
+
+        assert_eq!(point.neighbours().count(), 6);
+        assert_eq!(point.all_neighbours().count(), 26);
+        assert!(point
+            .neighbours()
+            .all(|neighbour| neighbour.manhattan_distance(&point) == 1));
+        assert!(point
+            .all_neighbours()
+            .all(|neighbour| neighbour.chebyshev_distance(&point) == 1));
+        assert_eq!(point.manhattan_distance(&Point3::ORIGIN), 6);
+    }
+
+    #[test]
+    fn bounding_box() {
+        let points = [Point3::new(1, 5, -2), Point3::new(3, 0, 0)];
+        let bounds = BoundingBox3::from_points(&points).unwrap();
+
+        assert_eq!(bounds.min, Point3::new(1, 0, -2));
+        assert_eq!(bounds.max, Point3::new(3, 5, 0));
+        assert_eq!(bounds.volume(), 3 * 6 * 3);
+        assert_eq!(bounds.iter().count() as u64, bounds.volume());
+        assert!(!bounds.contains(&Point3::ORIGIN));
+        assert!(bounds.expand(1).contains(&Point3::ORIGIN));
+    }
+
+    #[test]
+    fn exterior_flood_fill() {
+        // A hollow 3x3x3 shell; filling from outside must not reach the middle
+        let shell = BoundingBox3 {
+            min: Point3::ORIGIN,
+            max: Point3::new(2, 2, 2),
+        };
+        let solid = shell
+            .iter()
+            .filter(|&point| point != Point3::new(1, 1, 1))
+            .collect::<HashSet<_>>();
+        let space = shell.expand(1);
+
+        let outside = flood_fill(space.min, |point| {
+            point
+                .neighbours()
+                .filter(|neighbour| space.contains(neighbour) && !solid.contains(neighbour))
+                .collect::<Vec<_>>()
+        });
+
+        assert_eq!(outside.len() as u64, space.volume() - shell.volume());
+    }
+}
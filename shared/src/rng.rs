@@ -0,0 +1,47 @@
+/// A small, fast, seeded xorshift generator. Deterministic, so it suits
+/// sampling and property tests; not for anything needing real randomness.
+#[derive(Debug, Clone)]
+pub struct XorShift {
+    state: u64,
+}
+
+impl XorShift {
+    /// A generator starting from `seed`, which must be non-zero since zero
+    /// is a fixed point of xorshift
+    pub fn new(seed: u64) -> XorShift {
+        assert_ne!(seed, 0, "xorshift needs a non-zero seed");
+
+        XorShift { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+
+        self.state
+    }
+
+    /// A value in `0..bound`, with a slight bias towards low values that
+    /// doesn't matter for small bounds
+    pub fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deterministic() {
+        let mut a = XorShift::new(0x5eed);
+        let mut b = XorShift::new(0x5eed);
+
+        let values = (0..5).map(|_| a.next_u64()).collect::<Vec<_>>();
+
+        assert_eq!(values, (0..5).map(|_| b.next_u64()).collect::<Vec<_>>());
+        assert!(values.windows(2).all(|pair| pair[0] != pair[1]));
+        assert!((0..100).all(|_| a.below(7) < 7));
+    }
+}
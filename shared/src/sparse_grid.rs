@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+
+use crate::{GridView, Point};
+
+const ORTHOGONAL_OFFSETS: [(i64, i64); 4] = [(0, -1), (0, 1), (-1, 0), (1, 0)];
+const ALL_OFFSETS: [(i64, i64); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// An unbounded grid that only stores cells that have been set. Every other
+/// position reads as the default cell.
+#[derive(Debug, Clone)]
+pub struct SparseGrid<TCell> {
+    cells: HashMap<Point, TCell>,
+    default: TCell,
+    /// Inclusive (min, max) corners of the occupied cells
+    bounds: Option<(Point, Point)>,
+}
+
+impl<TCell> SparseGrid<TCell> {
+    pub fn new(default: TCell) -> SparseGrid<TCell> {
+        SparseGrid {
+            cells: HashMap::new(),
+            default,
+            bounds: None,
+        }
+    }
+
+    pub fn get(&self, position: &Point) -> &TCell {
+        self.cells.get(position).unwrap_or(&self.default)
+    }
+
+    pub fn contains(&self, position: &Point) -> bool {
+        self.cells.contains_key(position)
+    }
+
+    pub fn insert(&mut self, position: Point, cell: TCell) -> Option<TCell> {
+        self.bounds = Some(match self.bounds {
+            Some((min, max)) => (
+                Point::new(min.x.min(position.x), min.y.min(position.y)),
+                Point::new(max.x.max(position.x), max.y.max(position.y)),
+            ),
+            None => (position, position),
+        });
+
+        self.cells.insert(position, cell)
+    }
+
+    pub fn remove(&mut self, position: &Point) -> Option<TCell> {
+        let removed = self.cells.remove(position)?;
+
+        if let Some((min, max)) = self.bounds {
+            let on_edge = position.x == min.x
+                || position.x == max.x
+                || position.y == min.y
+                || position.y == max.y;
+
+            // Only cells on the edge of the bounding box can shrink it
+            if on_edge {
+                self.bounds = self.compute_bounds();
+            }
+        }
+
+        Some(removed)
+    }
+
+    /// Inclusive (min, max) corners of the occupied cells, or `None` if empty
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        self.bounds
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Point, &TCell)> {
+        self.cells.iter()
+    }
+
+    fn compute_bounds(&self) -> Option<(Point, Point)> {
+        let mut positions = self.cells.keys();
+        let first = *positions.next()?;
+
+        Some(positions.fold((first, first), |(min, max), position| {
+            (
+                Point::new(min.x.min(position.x), min.y.min(position.y)),
+                Point::new(max.x.max(position.x), max.y.max(position.y)),
+            )
+        }))
+    }
+}
+
+impl<TCell> GridView for SparseGrid<TCell> {
+    type Position = Point;
+    type Cell = TCell;
+
+    fn cell(&self, position: &Point) -> Option<&TCell> {
+        Some(self.get(position))
+    }
+
+    fn neighbours(&self, position: &Point) -> Vec<Point> {
+        ORTHOGONAL_OFFSETS
+            .iter()
+            .map(|(dx, dy)| Point::new(position.x + dx, position.y + dy))
+            .collect()
+    }
+
+    fn all_neighbours(&self, position: &Point) -> Vec<Point> {
+        ALL_OFFSETS
+            .iter()
+            .map(|(dx, dy)| Point::new(position.x + dx, position.y + dy))
+            .collect()
+    }
+
+    /// Renders the bounding box of the occupied cells
+    fn render(&self, to_char: impl Fn(&TCell) -> char) -> String {
+        let mut result = String::new();
+
+        if let Some((min, max)) = self.bounds {
+            for y in min.y..=max.y {
+                for x in min.x..=max.x {
+                    result.push(to_char(self.get(&Point::new(x, y))));
+                }
+
+                result.push('\n');
+            }
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracks_bounds() {
+        let mut grid = SparseGrid::new('.');
+        grid.insert(Point::new(0, 0), '#');
+        grid.insert(Point::new(-3, 2), '#');
+        grid.insert(Point::new(4, -1), '#');
+
+        assert_eq!(grid.bounds(), Some((Point::new(-3, -1), Point::new(4, 2))));
+
+        grid.remove(&Point::new(4, -1));
+
+        assert_eq!(grid.bounds(), Some((Point::new(-3, 0), Point::new(0, 2))));
+
+        grid.remove(&Point::new(0, 0));
+        grid.remove(&Point::new(-3, 2));
+
+        assert_eq!(grid.bounds(), None);
+    }
+
+    #[test]
+    fn default_cell() {
+        let mut grid = SparseGrid::new(0);
+        grid.insert(Point::new(-5, -5), 7);
+
+        assert_eq!(*grid.get(&Point::new(-5, -5)), 7);
+        assert_eq!(*grid.get(&Point::new(100, -100)), 0);
+    }
+
+    #[test]
+    fn render_occupied_window() {
+        let mut grid = SparseGrid::new(false);
+        grid.insert(Point::new(-1, -1), true);
+        grid.insert(Point::new(1, 0), true);
+
+        let result = grid.render(|&cell| if cell { '#' } else { '.' });
+
+        assert_eq!(result, "#..\n..#\n");
+    }
+}
@@ -0,0 +1,82 @@
+use crate::{Point, Vector};
+
+/// Wrap-around bounds where leaving one edge re-enters from the opposite one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Torus {
+    width: i64,
+    height: i64,
+}
+
+impl Torus {
+    pub fn new(width: i64, height: i64) -> Torus {
+        assert!(width > 0 && height > 0, "Torus must have a positive size");
+
+        Torus { width, height }
+    }
+
+    pub fn width(&self) -> i64 {
+        self.width
+    }
+
+    pub fn height(&self) -> i64 {
+        self.height
+    }
+
+    /// Brings any position, including negative ones, back onto the torus
+    pub fn wrap(&self, position: Point) -> Point {
+        Point::new(
+            position.x.rem_euclid(self.width),
+            position.y.rem_euclid(self.height),
+        )
+    }
+
+    pub fn step(&self, position: Point, velocity: Vector) -> Point {
+        self.wrap(position + velocity)
+    }
+
+    /// Moves by `velocity` `steps` times in one go. The displacement is reduced
+    /// modulo the torus size first, so large step counts can't overflow.
+    pub fn step_n(&self, position: Point, velocity: Vector, steps: i64) -> Point {
+        let dx = wrapping_product(velocity.x, steps, self.width);
+        let dy = wrapping_product(velocity.y, steps, self.height);
+
+        self.wrap(position + Vector::new(dx, dy))
+    }
+}
+
+/// `(a * b) mod modulus` without overflowing for large `b`
+fn wrapping_product(a: i64, b: i64, modulus: i64) -> i64 {
+    ((a.rem_euclid(modulus) as i128 * b.rem_euclid(modulus) as i128) % modulus as i128) as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_negative_positions() {
+        let torus = Torus::new(11, 7);
+
+        assert_eq!(torus.wrap(Point::new(-1, -8)), Point::new(10, 6));
+        assert_eq!(torus.wrap(Point::new(22, 7)), Point::new(0, 0));
+    }
+
+    #[test]
+    fn step_n_matches_repeated_steps() {
+        let torus = Torus::new(11, 7);
+        let start = Point::new(2, 4);
+        let velocity = Vector::new(2, -3);
+
+        let mut position = start;
+        for _ in 0..5 {
+            position = torus.step(position, velocity);
+        }
+
+        assert_eq!(position, Point::new(1, 3));
+        assert_eq!(torus.step_n(start, velocity, 5), position);
+        assert_eq!(
+            torus.step_n(start, velocity, 5 + 11 * 7 * 1_000_000_000_000),
+            position
+        );
+    }
+}
@@ -0,0 +1,171 @@
+use std::{collections::HashMap, hash::Hash};
+
+/// A prefix tree of words over any symbol type, stored as an arena of nodes.
+#[derive(Debug, Clone)]
+pub struct Trie<S> {
+    nodes: Vec<TrieNode<S>>,
+    word_count: usize,
+}
+
+#[derive(Debug, Clone)]
+struct TrieNode<S> {
+    children: HashMap<S, usize>,
+    /// Whether a word ends at this node
+    terminal: bool,
+}
+
+impl<S> TrieNode<S> {
+    fn new() -> Self {
+        Self {
+            children: HashMap::new(),
+            terminal: false,
+        }
+    }
+}
+
+const ROOT: usize = 0;
+
+impl<S: Eq + Hash> Default for Trie<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: Eq + Hash> Trie<S> {
+    pub fn new() -> Self {
+        Self {
+            nodes: vec![TrieNode::new()],
+            word_count: 0,
+        }
+    }
+
+    /// Adds `word`, returning whether it wasn't already present
+    pub fn insert(&mut self, word: impl IntoIterator<Item = S>) -> bool {
+        let mut current = ROOT;
+
+        for symbol in word {
+            current = match self.nodes[current].children.get(&symbol) {
+                Some(&child) => child,
+                None => {
+                    let child = self.nodes.len();
+                    self.nodes.push(TrieNode::new());
+                    self.nodes[current].children.insert(symbol, child);
+                    child
+                }
+            };
+        }
+
+        let is_new = !std::mem::replace(&mut self.nodes[current].terminal, true);
+        self.word_count += is_new as usize;
+
+        is_new
+    }
+
+    pub fn contains(&self, word: &[S]) -> bool {
+        let mut current = ROOT;
+
+        for symbol in word {
+            match self.nodes[current].children.get(symbol) {
+                Some(&child) => current = child,
+                None => return false,
+            }
+        }
+
+        self.nodes[current].terminal
+    }
+
+    /// Number of words in the trie
+    pub fn len(&self) -> usize {
+        self.word_count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.word_count == 0
+    }
+
+    /// Lengths of every word that `sequence[offset..]` starts with, shortest first
+    pub fn prefixes_at<'a>(
+        &'a self,
+        sequence: &'a [S],
+        offset: usize,
+    ) -> impl Iterator<Item = usize> + 'a {
+        let mut current = Some(ROOT);
+
+        sequence[offset..]
+            .iter()
+            .map_while(move |symbol| {
+                current = self.nodes[current?].children.get(symbol).copied();
+                current
+            })
+            .enumerate()
+            .filter(|&(_, node)| self.nodes[node].terminal)
+            .map(|(index, _)| index + 1)
+    }
+
+    /// Number of ways to split `sequence` into words from the trie. `buffer`
+    /// holds the per-suffix counts and can be reused across calls to avoid
+    /// allocating.
+    pub fn count_decompositions(&self, sequence: &[S], buffer: &mut Vec<u64>) -> u64 {
+        // buffer[i] = ways to split sequence[i..]
+        buffer.clear();
+        buffer.resize(sequence.len() + 1, 0);
+        buffer[sequence.len()] = 1;
+
+        for offset in (0..sequence.len()).rev() {
+            buffer[offset] = self
+                .prefixes_at(sequence, offset)
+                .map(|length| buffer[offset + length])
+                .sum();
+        }
+
+        buffer[0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trie(words: &[&str]) -> Trie<u8> {
+        let mut trie = Trie::new();
+
+        for word in words {
+            trie.insert(word.bytes());
+        }
+
+        trie
+    }
+
+    #[test]
+    fn insert_and_contains() {
+        let mut trie = trie(&["ab", "abc"]);
+
+        assert_eq!(trie.len(), 2);
+        assert!(!trie.insert(b"ab".iter().copied()));
+        assert!(trie.contains(b"abc"));
+        assert!(!trie.contains(b"a"));
+        assert!(!trie.contains(b"abcd"));
+    }
+
+    #[test]
+    fn prefixes() {
+        let trie = trie(&["b", "ba", "bac", "c"]);
+
+        assert_eq!(
+            trie.prefixes_at(b"abacus", 1).collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        assert_eq!(trie.prefixes_at(b"abacus", 0).count(), 0);
+    }
+
+    #[test]
+    fn decompositions() {
+        let trie = trie(&["r", "wr", "b", "g", "bwu", "rb", "gb", "br"]);
+        let mut buffer = vec![];
+
+        assert_eq!(trie.count_decompositions(b"brwrr", &mut buffer), 2);
+        assert_eq!(trie.count_decompositions(b"rrbgbr", &mut buffer), 6);
+        assert_eq!(trie.count_decompositions(b"ubwu", &mut buffer), 0);
+        assert_eq!(trie.count_decompositions(b"", &mut buffer), 1);
+    }
+}
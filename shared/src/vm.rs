@@ -0,0 +1,346 @@
+//! A small register-machine interpreter. Each puzzle supplies its own
+//! instruction set; the [`Machine`] takes care of the instruction pointer,
+//! cycle counting, breakpoints and tracing.
+
+use std::{
+    collections::BTreeSet,
+    fmt::{self, Debug, Display},
+};
+
+/// What the instruction pointer does after an instruction executes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flow {
+    Next,
+    Jump(usize),
+    Halt,
+}
+
+/// One instruction of a pluggable instruction set. `Display` gives its
+/// mnemonic form, such as `adv 3`, which the disassembler and traces use.
+pub trait Instruction: Display {
+    /// Registers and anything else the instructions read or write
+    type State: Clone + Debug;
+    /// Values emitted by output instructions
+    type Output;
+
+    fn execute(&self, state: &mut Self::State, output: &mut Vec<Self::Output>) -> Flow;
+
+    /// Clock cycles the instruction takes to complete
+    fn cycles(&self) -> usize {
+        1
+    }
+}
+
+/// Why [`Machine::run`] returned
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stop {
+    Halted,
+    /// About to execute the instruction at this address
+    Breakpoint(usize),
+}
+
+/// A record of one executed instruction
+#[derive(Debug, Clone)]
+pub struct TraceEntry<S> {
+    /// Cycle the instruction started on, counting from 0
+    pub cycle: usize,
+    pub address: usize,
+    pub mnemonic: String,
+    /// The state once the instruction completed
+    pub state: S,
+}
+
+impl<S: Debug> Display for TraceEntry<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:>6}  {:>4}  {:<10}  {:?}",
+            self.cycle, self.address, self.mnemonic, self.state
+        )
+    }
+}
+
+/// Runs a program of `I` instructions against a state.
+pub struct Machine<I: Instruction> {
+    program: Vec<I>,
+    state: I::State,
+    instruction_pointer: usize,
+    cycle: usize,
+    halted: bool,
+    output: Vec<I::Output>,
+    breakpoints: BTreeSet<usize>,
+    /// Stopped at a breakpoint by `run`, which should step past it next time
+    resuming: bool,
+    trace: Option<Vec<TraceEntry<I::State>>>,
+}
+
+impl<I: Instruction> Machine<I> {
+    pub fn new(program: Vec<I>, state: I::State) -> Machine<I> {
+        Machine {
+            program,
+            state,
+            instruction_pointer: 0,
+            cycle: 0,
+            halted: false,
+            output: vec![],
+            breakpoints: BTreeSet::new(),
+            resuming: false,
+            trace: None,
+        }
+    }
+
+    pub fn program(&self) -> &[I] {
+        &self.program
+    }
+
+    pub fn state(&self) -> &I::State {
+        &self.state
+    }
+
+    pub fn state_mut(&mut self) -> &mut I::State {
+        &mut self.state
+    }
+
+    pub fn instruction_pointer(&self) -> usize {
+        self.instruction_pointer
+    }
+
+    /// Cycles elapsed since the machine started or was last reset
+    pub fn cycle(&self) -> usize {
+        self.cycle
+    }
+
+    pub fn output(&self) -> &[I::Output] {
+        &self.output
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.halted || self.instruction_pointer >= self.program.len()
+    }
+
+    /// Starts over from the first instruction with `state`, clearing the
+    /// output and trace but keeping breakpoints
+    pub fn reset(&mut self, state: I::State) {
+        self.state = state;
+        self.instruction_pointer = 0;
+        self.cycle = 0;
+        self.halted = false;
+        self.resuming = false;
+        self.output.clear();
+
+        if let Some(trace) = &mut self.trace {
+            trace.clear();
+        }
+    }
+
+    /// Executes a single instruction, returning `false` if the machine had already halted
+    pub fn step(&mut self) -> bool {
+        if self.is_halted() {
+            return false;
+        }
+
+        self.resuming = false;
+
+        let address = self.instruction_pointer;
+        let instruction = &self.program[address];
+        let flow = instruction.execute(&mut self.state, &mut self.output);
+
+        if let Some(trace) = &mut self.trace {
+            trace.push(TraceEntry {
+                cycle: self.cycle,
+                address,
+                mnemonic: instruction.to_string(),
+                state: self.state.clone(),
+            });
+        }
+
+        self.cycle += instruction.cycles();
+
+        match flow {
+            Flow::Next => self.instruction_pointer += 1,
+            Flow::Jump(target) => self.instruction_pointer = target,
+            Flow::Halt => self.halted = true,
+        }
+
+        true
+    }
+
+    /// Steps until the machine halts or reaches a breakpoint, including one on
+    /// the instruction it's about to execute. Calling `run` again after a
+    /// breakpoint resumes past it.
+    pub fn run(&mut self) -> Stop {
+        loop {
+            if !self.resuming
+                && !self.is_halted()
+                && self.breakpoints.contains(&self.instruction_pointer)
+            {
+                self.resuming = true;
+                return Stop::Breakpoint(self.instruction_pointer);
+            }
+
+            if !self.step() {
+                return Stop::Halted;
+            }
+        }
+    }
+
+    pub fn add_breakpoint(&mut self, address: usize) {
+        self.breakpoints.insert(address);
+    }
+
+    pub fn remove_breakpoint(&mut self, address: usize) {
+        self.breakpoints.remove(&address);
+    }
+
+    /// Starts recording every executed instruction, discarding any earlier trace
+    pub fn enable_trace(&mut self) {
+        self.trace = Some(vec![]);
+    }
+
+    /// The recorded trace, empty unless [`Machine::enable_trace`] was called
+    pub fn trace(&self) -> &[TraceEntry<I::State>] {
+        self.trace.as_deref().unwrap_or_default()
+    }
+
+    pub fn disassemble(&self) -> String {
+        disassemble(&self.program)
+    }
+}
+
+/// The program in mnemonic form, one instruction after another separated by `; `
+pub fn disassemble<I: Display>(program: &[I]) -> String {
+    program
+        .iter()
+        .map(|instruction| instruction.to_string())
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts a register down to zero, emitting it each time round
+    enum Countdown {
+        Emit,
+        Decrement,
+        JumpIfNonZero(usize),
+        Halt,
+    }
+
+    impl Display for Countdown {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Countdown::Emit => write!(f, "out"),
+                Countdown::Decrement => write!(f, "dec"),
+                Countdown::JumpIfNonZero(target) => write!(f, "jnz {target}"),
+                Countdown::Halt => write!(f, "hlt"),
+            }
+        }
+    }
+
+    impl Instruction for Countdown {
+        type State = u32;
+        type Output = u32;
+
+        fn execute(&self, state: &mut u32, output: &mut Vec<u32>) -> Flow {
+            match self {
+                Countdown::Emit => output.push(*state),
+                Countdown::Decrement => *state -= 1,
+                Countdown::JumpIfNonZero(target) if *state != 0 => return Flow::Jump(*target),
+                Countdown::JumpIfNonZero(_) => {}
+                Countdown::Halt => return Flow::Halt,
+            }
+
+            Flow::Next
+        }
+
+        fn cycles(&self) -> usize {
+            match self {
+                Countdown::Decrement => 2,
+                _ => 1,
+            }
+        }
+    }
+
+    fn countdown(from: u32) -> Machine<Countdown> {
+        let program = vec![
+            Countdown::Emit,
+            Countdown::Decrement,
+            Countdown::JumpIfNonZero(0),
+            Countdown::Halt,
+            Countdown::Emit,
+        ];
+
+        Machine::new(program, from)
+    }
+
+    #[test]
+    fn runs_to_halt() {
+        let mut machine = countdown(3);
+
+        assert_eq!(machine.run(), Stop::Halted);
+        assert_eq!(machine.output(), &[3, 2, 1]);
+        assert_eq!(machine.cycle(), 13);
+        assert!(!machine.step());
+    }
+
+    #[test]
+    fn breakpoints_resume() {
+        let mut machine = countdown(3);
+        machine.add_breakpoint(1);
+
+        assert_eq!(machine.run(), Stop::Breakpoint(1));
+        assert_eq!(machine.output(), &[3]);
+        assert_eq!(machine.run(), Stop::Breakpoint(1));
+        assert_eq!(*machine.state(), 2);
+
+        machine.remove_breakpoint(1);
+        assert_eq!(machine.run(), Stop::Halted);
+        assert_eq!(machine.output(), &[3, 2, 1]);
+
+        machine.reset(1);
+        assert_eq!(machine.run(), Stop::Halted);
+        assert_eq!(machine.output(), &[1]);
+    }
+
+    #[test]
+    fn breakpoint_at_entry_point() {
+        let mut machine = countdown(2);
+        machine.add_breakpoint(0);
+
+        assert_eq!(machine.run(), Stop::Breakpoint(0));
+        assert_eq!(machine.output(), &[]);
+        assert_eq!(machine.run(), Stop::Breakpoint(0));
+        assert_eq!(machine.output(), &[2]);
+
+        // Stepping by hand onto a breakpoint doesn't skip it
+        machine.step();
+        machine.step();
+        machine.step();
+        assert_eq!(machine.instruction_pointer(), 3);
+        machine.add_breakpoint(3);
+        assert_eq!(machine.run(), Stop::Breakpoint(3));
+        assert_eq!(machine.run(), Stop::Halted);
+    }
+
+    #[test]
+    fn trace_and_disassembly() {
+        let mut machine = countdown(1);
+        machine.enable_trace();
+        machine.run();
+
+        let trace = machine
+            .trace()
+            .iter()
+            .map(|entry| (entry.cycle, entry.address, entry.state))
+            .collect::<Vec<_>>();
+
+        assert_eq!(trace, vec![(0, 0, 1), (1, 1, 0), (3, 2, 0), (4, 3, 0)]);
+        assert_eq!(
+            machine.trace()[1].to_string(),
+            "     1     1  dec         0"
+        );
+        assert_eq!(machine.disassemble(), "out; dec; jnz 0; hlt; out");
+    }
+}